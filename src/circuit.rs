@@ -0,0 +1,370 @@
+//! A circuit breaker which only counts failures of specific error
+//! types. After enough consecutive failures of one of the `Trips`
+//! types, the circuit opens and calls fail immediately with
+//! `CircuitOpen` until the reset timeout has passed. Then a single
+//! call is let through as a probe, which closes the circuit again
+//! if it does not fail with one of the `Trips` types.
+//!
+//! ```
+//! use std::time::Duration;
+//!
+//! use terrors::circuit::{CircuitBreaker, CircuitOpen};
+//! use terrors::common::Timeout;
+//! use terrors::retry::ManualClock;
+//! use terrors::OneOf;
+//!
+//! #[derive(Debug)]
+//! struct Validation;
+//!
+//! let breaker = CircuitBreaker::<(Timeout,)>::new(1, Duration::from_secs(30))
+//!     .with_clock(ManualClock::new());
+//!
+//! let call = || Err::<(), _>(OneOf::<(Timeout, Validation)>::new(Timeout::new()));
+//!
+//! let first: OneOf<(Timeout, Validation, CircuitOpen)> = breaker.call(call).unwrap_err();
+//! assert!(first.narrow::<Timeout, _>().is_ok());
+//!
+//! let second: OneOf<(Timeout, Validation, CircuitOpen)> = breaker.call(call).unwrap_err();
+//! assert!(second.narrow::<CircuitOpen, _>().is_ok());
+//! ```
+use core::any::type_name;
+use core::fmt;
+use core::marker::PhantomData;
+use core::time::Duration;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::sync::Mutex;
+
+use crate::retry::{Clock, SystemClock};
+use crate::type_set::{AnyFold, Contains, SupersetOf, TypeNameFold, TypeSet};
+use crate::OneOf;
+
+/// A call was rejected without being made because the circuit is open.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CircuitOpen {
+    retry_after: Duration,
+}
+
+impl CircuitOpen {
+    /// Returns how long it will be until the circuit lets a probe through.
+    /// This is zero when a probe is already in flight.
+    pub fn retry_after(&self) -> Duration {
+        self.retry_after
+    }
+}
+
+impl fmt::Display for CircuitOpen {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "circuit breaker is open")
+    }
+}
+
+impl Error for CircuitOpen {}
+
+/// The state of a `CircuitBreaker`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CircuitState {
+    /// Calls are made as usual.
+    Closed,
+    /// Calls are rejected with `CircuitOpen`.
+    Open,
+    /// The next call is let through to probe whether the circuit can close.
+    HalfOpen,
+}
+
+/// The outcomes of the calls made through a `CircuitBreaker`,
+/// with failures broken down by the type of their variant.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CircuitStats {
+    successes: u64,
+    rejected: u64,
+    trips: u64,
+    failures: BTreeMap<&'static str, u64>,
+}
+
+impl CircuitStats {
+    /// Returns the number of calls that succeeded.
+    pub fn successes(&self) -> u64 {
+        self.successes
+    }
+
+    /// Returns the number of calls that were rejected with `CircuitOpen`.
+    pub fn rejected(&self) -> u64 {
+        self.rejected
+    }
+
+    /// Returns the number of calls that failed with one of the `Trips` types.
+    pub fn trips(&self) -> u64 {
+        self.trips
+    }
+
+    /// Returns the number of calls that failed with any type.
+    pub fn failures(&self) -> u64 {
+        self.failures.values().sum()
+    }
+
+    /// Returns the number of calls that failed with a `T`.
+    pub fn failures_of<T: ?Sized>(&self) -> u64 {
+        self.failures.get(type_name::<T>()).copied().unwrap_or(0)
+    }
+
+    /// Returns the name of each type that a call failed
+    /// with, along with the number of those failures.
+    pub fn failures_by_type(&self) -> impl Iterator<Item = (&'static str, u64)> + '_ {
+        self.failures.iter().map(|(name, count)| (*name, *count))
+    }
+}
+
+#[derive(Debug)]
+enum State {
+    Closed { failures: u32 },
+    Open { until: Duration },
+    HalfOpen,
+}
+
+#[derive(Debug)]
+struct Inner {
+    state: State,
+    /// Incremented whenever `state` moves to a different variant, so that
+    /// the outcome of a call which was let through before then is ignored.
+    generation: u64,
+    stats: CircuitStats,
+}
+
+impl Inner {
+    fn transition(&mut self, state: State) {
+        self.state = state;
+        self.generation += 1;
+    }
+}
+
+enum Outcome {
+    Success,
+    Failure { trip: bool, type_name: &'static str },
+    Panic,
+}
+
+/// Lets a call through, and records its outcome. If the call panics,
+/// the permit is dropped without an outcome and records a `Panic`.
+struct Permit<'a, Trips: ?Sized, C: Clock> {
+    breaker: &'a CircuitBreaker<Trips, C>,
+    generation: u64,
+    recorded: bool,
+}
+
+impl<'a, Trips: ?Sized, C: Clock> Permit<'a, Trips, C> {
+    fn new(breaker: &'a CircuitBreaker<Trips, C>, generation: u64) -> Permit<'a, Trips, C> {
+        Permit {
+            breaker,
+            generation,
+            recorded: false,
+        }
+    }
+
+    fn record(mut self, outcome: Outcome) {
+        self.recorded = true;
+        self.breaker.record(self.generation, outcome);
+    }
+}
+
+impl<Trips: ?Sized, C: Clock> Drop for Permit<'_, Trips, C> {
+    fn drop(&mut self) {
+        if !self.recorded {
+            self.breaker.record(self.generation, Outcome::Panic);
+        }
+    }
+}
+
+/// Opens after `failure_threshold` consecutive calls fail with one of
+/// the `Trips` types. Failures of any other type show that the callee
+/// is still responsive, so they are treated like successes. The calls
+/// do not need to share an error set, nor include all of the `Trips`
+/// types in it.
+///
+/// A call which panics is treated like a failure of one of the `Trips`
+/// types, although it is not counted in the `CircuitStats`. Only the
+/// outcome of a call that was let through in the current state can
+/// change the state, so a slow call which fails after the circuit has
+/// opened does not close it again.
+pub struct CircuitBreaker<Trips: ?Sized, C = SystemClock> {
+    failure_threshold: u32,
+    reset_timeout: Duration,
+    clock: C,
+    inner: Mutex<Inner>,
+    trips: PhantomData<fn(&Trips)>,
+}
+
+impl<Trips> CircuitBreaker<Trips>
+where
+    Trips: TypeSet + ?Sized,
+{
+    /// Create a new, closed `CircuitBreaker`. It opens after
+    /// `failure_threshold` consecutive failures, and waits for
+    /// `reset_timeout` before it lets a probe through.
+    pub fn new(failure_threshold: u32, reset_timeout: Duration) -> CircuitBreaker<Trips> {
+        CircuitBreaker {
+            failure_threshold,
+            reset_timeout,
+            clock: SystemClock,
+            inner: Mutex::new(Inner {
+                state: State::Closed { failures: 0 },
+                generation: 0,
+                stats: CircuitStats::default(),
+            }),
+            trips: PhantomData,
+        }
+    }
+}
+
+impl<Trips, C> CircuitBreaker<Trips, C>
+where
+    Trips: TypeSet + ?Sized,
+    C: Clock,
+{
+    /// Tell the time using a different `Clock`.
+    pub fn with_clock<C2: Clock>(self, clock: C2) -> CircuitBreaker<Trips, C2> {
+        CircuitBreaker {
+            failure_threshold: self.failure_threshold,
+            reset_timeout: self.reset_timeout,
+            clock,
+            inner: self.inner,
+            trips: PhantomData,
+        }
+    }
+
+    /// Borrow the `Clock` used to tell the time.
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// Returns the current state of the circuit.
+    pub fn state(&self) -> CircuitState {
+        match self.inner.lock().unwrap().state {
+            State::Closed { .. } => CircuitState::Closed,
+            State::Open { until } if self.clock.now() >= until => CircuitState::HalfOpen,
+            State::Open { .. } | State::HalfOpen => CircuitState::Open,
+        }
+    }
+
+    /// Returns the outcomes of the calls made so far.
+    pub fn stats(&self) -> CircuitStats {
+        self.inner.lock().unwrap().stats.clone()
+    }
+
+    /// Make the call if the circuit allows it, and record its outcome.
+    ///
+    /// The variants of the call's error are merged along with
+    /// `CircuitOpen` into the `Other` set, which must be a superset
+    /// of all of them. This may be the same set as the call's error,
+    /// for example when it already includes the `CircuitOpen` of an
+    /// inner `CircuitBreaker`.
+    pub fn call<T, E, Other, F, Index, OpenIndex>(&self, operation: F) -> Result<T, OneOf<Other>>
+    where
+        E: TypeSet + ?Sized,
+        Other: TypeSet + ?Sized,
+        F: FnOnce() -> Result<T, OneOf<E>>,
+        Trips::Variants: AnyFold,
+        E::Variants: AnyFold,
+        Other::Variants: SupersetOf<E::Variants, Index> + Contains<CircuitOpen, OpenIndex>,
+    {
+        let permit = match self.acquire() {
+            Ok(permit) => permit,
+            Err(open) => return Err(OneOf::new(open)),
+        };
+
+        match operation() {
+            Ok(t) => {
+                permit.record(Outcome::Success);
+                Ok(t)
+            }
+            Err(error) => {
+                permit.record(Outcome::Failure {
+                    trip: Trips::Variants::contains_type_id(error.value.type_id_fold()),
+                    type_name: error.value.type_name_fold(),
+                });
+
+                Err(error.broaden())
+            }
+        }
+    }
+
+    fn acquire(&self) -> Result<Permit<'_, Trips, C>, CircuitOpen> {
+        let now = self.clock.now();
+        let mut inner = self.inner.lock().unwrap();
+
+        let retry_after = match inner.state {
+            State::Closed { .. } => return Ok(Permit::new(self, inner.generation)),
+            State::Open { until } if now >= until => {
+                inner.transition(State::HalfOpen);
+                return Ok(Permit::new(self, inner.generation));
+            }
+            State::Open { until } => until - now,
+            State::HalfOpen => Duration::ZERO,
+        };
+
+        inner.stats.rejected += 1;
+
+        Err(CircuitOpen { retry_after })
+    }
+}
+
+impl<Trips: ?Sized, C: Clock> CircuitBreaker<Trips, C> {
+    /// Records the outcome of a call that was let through in the given
+    /// generation. Only the stats are updated if the state has changed
+    /// since then.
+    fn record(&self, generation: u64, outcome: Outcome) {
+        let now = self.clock.now();
+        let mut inner = self.inner.lock().unwrap();
+
+        let trip = match outcome {
+            Outcome::Success => {
+                inner.stats.successes += 1;
+                false
+            }
+            Outcome::Failure { trip, type_name } => {
+                *inner.stats.failures.entry(type_name).or_insert(0) += 1;
+                if trip {
+                    inner.stats.trips += 1;
+                }
+                trip
+            }
+            Outcome::Panic => true,
+        };
+
+        if inner.generation != generation {
+            return;
+        }
+
+        match inner.state {
+            State::Closed { failures } if trip => {
+                let failures = failures + 1;
+                if failures >= self.failure_threshold {
+                    inner.transition(State::Open {
+                        until: now + self.reset_timeout,
+                    });
+                } else {
+                    inner.state = State::Closed { failures };
+                }
+            }
+            State::Closed { .. } => inner.state = State::Closed { failures: 0 },
+            State::HalfOpen if trip => inner.transition(State::Open {
+                until: now + self.reset_timeout,
+            }),
+            State::HalfOpen => inner.transition(State::Closed { failures: 0 }),
+            // no calls are let through while the circuit is open
+            State::Open { .. } => {}
+        }
+    }
+}
+
+impl<Trips: ?Sized, C: fmt::Debug> fmt::Debug for CircuitBreaker<Trips, C> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("CircuitBreaker")
+            .field("failure_threshold", &self.failure_threshold)
+            .field("reset_timeout", &self.reset_timeout)
+            .field("clock", &self.clock)
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
@@ -0,0 +1,264 @@
+//! A shared vocabulary of error types for failures that show up in
+//! almost every codebase. When independent crates use these rather
+//! than defining their own, their error sets contain the very same
+//! types, which can then be deduplicated when the sets are combined
+//! with `broaden`, `subset` and `split`.
+//!
+//! With the `serde` feature enabled, every type in this module
+//! implements `Serialize` and `Deserialize`.
+//!
+//! ```
+//! use std::time::Duration;
+//!
+//! use terrors::common::{NotFound, Timeout};
+//! use terrors::OneOf;
+//!
+//! struct User;
+//!
+//! fn load_user(id: u64) -> Result<(), OneOf<(NotFound<User>, Timeout)>> {
+//!     if id == 0 {
+//!         return Err(OneOf::new(NotFound::with_key(id)));
+//!     }
+//!     Err(OneOf::new(Timeout::after(Duration::from_secs(5))))
+//! }
+//!
+//! let not_found = load_user(0).unwrap_err().narrow::<NotFound<User>, _>().unwrap();
+//! assert_eq!(not_found.to_string(), "User 0 not found");
+//! ```
+//!
+//! With the `io` feature enabled, the `io` module has `NotFound`,
+//! `AlreadyExists` and `TimedOut` types as well. Those wrap the `io::Error` of a failed io operation
+//! and are what the `fs` and `net` wrappers return, while the types
+//! here describe the failure without tying it to io, and are what
+//! `retry`, `circuit` and `futures` return. Each of those converts
+//! into its counterpart here, so that they can be merged into a
+//! single variant with `map_variant`:
+//!
+//! ```
+//! # #[cfg(feature = "io")] {
+//! use std::io::{self, ErrorKind};
+//!
+//! use terrors::common::Timeout;
+//! use terrors::io::{classify, TimedOut};
+//!
+//! let error = classify(io::Error::from(ErrorKind::TimedOut));
+//! let error = error.map_variant(|timed_out: TimedOut| Timeout::from(timed_out));
+//! assert!(error.narrow::<Timeout, _>().is_ok());
+//! # }
+//! ```
+use core::any::type_name;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::time::Duration;
+use std::error::Error;
+
+#[cfg(feature = "io")]
+use crate::io;
+use crate::type_set::ShortTypeName;
+
+/// An operation did not complete in time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Timeout {
+    after: Option<Duration>,
+}
+
+impl Timeout {
+    /// Create a new `Timeout` without a known deadline.
+    pub fn new() -> Timeout {
+        Timeout { after: None }
+    }
+
+    /// Create a new `Timeout` for an operation that
+    /// was given the `after` duration to complete.
+    pub fn after(after: Duration) -> Timeout {
+        Timeout { after: Some(after) }
+    }
+
+    /// Returns the duration that the operation was given, if it is known.
+    pub fn duration(&self) -> Option<Duration> {
+        self.after
+    }
+}
+
+impl fmt::Display for Timeout {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.after {
+            Some(after) => write!(formatter, "timed out after {after:?}"),
+            None => write!(formatter, "timed out"),
+        }
+    }
+}
+
+impl Error for Timeout {}
+
+/// The deadline of an `io::TimedOut` is not known.
+#[cfg(feature = "io")]
+impl From<io::TimedOut> for Timeout {
+    fn from(_: io::TimedOut) -> Timeout {
+        Timeout::new()
+    }
+}
+
+/// An operation was cancelled before it could complete.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cancelled;
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "cancelled")
+    }
+}
+
+impl Error for Cancelled {}
+
+/// An operation was retried until it ran out of attempts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RetriesExhausted {
+    attempts: Option<u32>,
+}
+
+impl RetriesExhausted {
+    /// Create a new `RetriesExhausted` without a known number of attempts.
+    pub fn new() -> RetriesExhausted {
+        RetriesExhausted { attempts: None }
+    }
+
+    /// Create a new `RetriesExhausted` after the given number of attempts.
+    pub fn after_attempts(attempts: u32) -> RetriesExhausted {
+        RetriesExhausted {
+            attempts: Some(attempts),
+        }
+    }
+
+    /// Returns the number of attempts that were made, if it is known.
+    pub fn attempts(&self) -> Option<u32> {
+        self.attempts
+    }
+}
+
+impl fmt::Display for RetriesExhausted {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.attempts {
+            Some(attempts) => write!(formatter, "retries exhausted after {attempts} attempts"),
+            None => write!(formatter, "retries exhausted"),
+        }
+    }
+}
+
+impl Error for RetriesExhausted {}
+
+macro_rules! resource_errors {
+    ($($(#[$meta:meta])* $name:ident => $message:literal,)*) => {
+        $(
+            $(#[$meta])*
+            ///
+            /// The type parameter names the kind of resource, so
+            /// that `NotFound<User>` and `NotFound<Order>` can be
+            /// told apart within a single set. It is only used as
+            /// a marker, and does not need to implement anything.
+            #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+            #[cfg_attr(feature = "serde", serde(bound = ""))]
+            pub struct $name<T: ?Sized> {
+                key: Option<String>,
+                #[cfg_attr(feature = "serde", serde(skip))]
+                resource: PhantomData<fn() -> T>,
+            }
+
+            impl<T: ?Sized> $name<T> {
+                /// Create a new error without a key.
+                pub fn new() -> $name<T> {
+                    $name {
+                        key: None,
+                        resource: PhantomData,
+                    }
+                }
+
+                /// Create a new error for the resource with the given key.
+                pub fn with_key(key: impl fmt::Display) -> $name<T> {
+                    $name {
+                        key: Some(key.to_string()),
+                        resource: PhantomData,
+                    }
+                }
+
+                /// Returns the key of the resource, if there is one.
+                pub fn key(&self) -> Option<&str> {
+                    self.key.as_deref()
+                }
+            }
+
+            impl<T: ?Sized> Default for $name<T> {
+                fn default() -> $name<T> {
+                    $name::new()
+                }
+            }
+
+            impl<T: ?Sized> Clone for $name<T> {
+                fn clone(&self) -> $name<T> {
+                    $name {
+                        key: self.key.clone(),
+                        resource: PhantomData,
+                    }
+                }
+            }
+
+            impl<T: ?Sized> PartialEq for $name<T> {
+                fn eq(&self, other: &$name<T>) -> bool {
+                    self.key == other.key
+                }
+            }
+
+            impl<T: ?Sized> Eq for $name<T> {}
+
+            impl<T: ?Sized> Hash for $name<T> {
+                fn hash<H: Hasher>(&self, state: &mut H) {
+                    self.key.hash(state)
+                }
+            }
+
+            impl<T: ?Sized> fmt::Debug for $name<T> {
+                fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    formatter
+                        .debug_struct(stringify!($name))
+                        .field("resource", &format_args!("{}", ShortTypeName(type_name::<T>())))
+                        .field("key", &self.key)
+                        .finish()
+                }
+            }
+
+            impl<T: ?Sized> fmt::Display for $name<T> {
+                fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    let resource = ShortTypeName(type_name::<T>());
+                    match &self.key {
+                        Some(key) => write!(formatter, "{} {} {}", resource, key, $message),
+                        None => write!(formatter, "{} {}", resource, $message),
+                    }
+                }
+            }
+
+            impl<T: ?Sized> Error for $name<T> {}
+
+            /// The path that was involved, if there is one, becomes the key.
+            #[cfg(feature = "io")]
+            impl<T: ?Sized> From<io::$name> for $name<T> {
+                fn from(error: io::$name) -> $name<T> {
+                    $name {
+                        key: error.path().map(|path| path.display().to_string()),
+                        resource: PhantomData,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+resource_errors! {
+    /// A resource could not be found.
+    NotFound => "not found",
+    /// A resource could not be created because it already exists.
+    AlreadyExists => "already exists",
+}
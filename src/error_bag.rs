@@ -0,0 +1,262 @@
+use core::fmt;
+use std::error::Error;
+
+use crate::type_set::{Narrow, TupleForm, TypeSet};
+use crate::{IntoOneOf, OneOf};
+
+/// A collection of `OneOf` errors, for code which should report
+/// every failure rather than stopping at the first one, such as
+/// validation.
+///
+/// ```
+/// use terrors::{CollectErrors, ErrorBag, OneOf};
+///
+/// #[derive(Debug)]
+/// struct Empty;
+/// #[derive(Debug)]
+/// struct TooLong(usize);
+///
+/// fn check(name: &str) -> Result<&str, OneOf<(Empty, TooLong)>> {
+///     match name.len() {
+///         0 => Err(OneOf::new(Empty)),
+///         len if len > 5 => Err(OneOf::new(TooLong(len))),
+///         _ => Ok(name),
+///     }
+/// }
+///
+/// let bag: ErrorBag<(Empty, TooLong)> = ["ok", "", "much too long", ""]
+///     .into_iter()
+///     .map(check)
+///     .collect_errors()
+///     .unwrap_err();
+///
+/// assert_eq!(bag.len(), 3);
+/// assert_eq!(bag.iter_of::<Empty, _>().count(), 2);
+///
+/// let (too_long, rest) = bag.narrow_all::<TooLong, _>();
+/// assert_eq!(too_long[0].0, 13);
+/// assert_eq!(rest.len(), 2);
+/// ```
+pub struct ErrorBag<E: TypeSet + ?Sized> {
+    errors: Vec<OneOf<E>>,
+}
+
+impl<E> ErrorBag<E>
+where
+    E: TypeSet + ?Sized,
+{
+    /// Create a new, empty `ErrorBag`.
+    pub fn new() -> ErrorBag<E> {
+        ErrorBag { errors: vec![] }
+    }
+
+    /// Add an error, which may be any of the types in the set
+    /// or a `OneOf` whose variants are a subset of it.
+    pub fn push<T, Index>(&mut self, error: T)
+    where
+        T: IntoOneOf<E, Index>,
+    {
+        self.errors.push(error.into_one_of())
+    }
+
+    /// Returns the number of errors.
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// Returns `true` if there are no errors.
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Iterate over the errors, in the order that they were added.
+    pub fn iter(&self) -> core::slice::Iter<'_, OneOf<E>> {
+        self.errors.iter()
+    }
+
+    /// Iterate over the errors of the `T` type, in the order that they were added.
+    pub fn iter_of<'a, T: 'a, Index>(&'a self) -> impl Iterator<Item = &'a T>
+    where
+        E::Variants: Narrow<T, Index>,
+    {
+        self.errors.iter().filter_map(OneOf::narrow_ref::<T, Index>)
+    }
+
+    /// Split out every error of the `T` type, returning them
+    /// along with a bag of the remaining errors.
+    #[allow(clippy::type_complexity)]
+    pub fn narrow_all<T, Index>(
+        self,
+    ) -> (
+        Vec<T>,
+        ErrorBag<<<E::Variants as Narrow<T, Index>>::Remainder as TupleForm>::Tuple>,
+    )
+    where
+        E::Variants: Narrow<T, Index>,
+    {
+        let mut narrowed = vec![];
+        let mut rest = ErrorBag::new();
+
+        for error in self.errors {
+            match error.narrow::<T, Index>() {
+                Ok(t) => narrowed.push(t),
+                Err(remainder) => rest.errors.push(remainder),
+            }
+        }
+
+        (narrowed, rest)
+    }
+
+    /// Returns `Ok(t)` if the bag is empty, and the bag otherwise.
+    pub fn into_result<T>(self, t: T) -> Result<T, ErrorBag<E>> {
+        if self.is_empty() {
+            Ok(t)
+        } else {
+            Err(self)
+        }
+    }
+
+    /// Return the errors, in the order that they were added.
+    pub fn into_vec(self) -> Vec<OneOf<E>> {
+        self.errors
+    }
+}
+
+impl<E> Default for ErrorBag<E>
+where
+    E: TypeSet + ?Sized,
+{
+    fn default() -> ErrorBag<E> {
+        ErrorBag::new()
+    }
+}
+
+impl<E> Clone for ErrorBag<E>
+where
+    E: TypeSet + ?Sized,
+    OneOf<E>: Clone,
+{
+    fn clone(&self) -> ErrorBag<E> {
+        ErrorBag {
+            errors: self.errors.clone(),
+        }
+    }
+}
+
+impl<E> From<Vec<OneOf<E>>> for ErrorBag<E>
+where
+    E: TypeSet + ?Sized,
+{
+    fn from(errors: Vec<OneOf<E>>) -> ErrorBag<E> {
+        ErrorBag { errors }
+    }
+}
+
+impl<E> Extend<OneOf<E>> for ErrorBag<E>
+where
+    E: TypeSet + ?Sized,
+{
+    fn extend<I: IntoIterator<Item = OneOf<E>>>(&mut self, errors: I) {
+        self.errors.extend(errors)
+    }
+}
+
+impl<E> FromIterator<OneOf<E>> for ErrorBag<E>
+where
+    E: TypeSet + ?Sized,
+{
+    fn from_iter<I: IntoIterator<Item = OneOf<E>>>(errors: I) -> ErrorBag<E> {
+        ErrorBag {
+            errors: errors.into_iter().collect(),
+        }
+    }
+}
+
+impl<E> IntoIterator for ErrorBag<E>
+where
+    E: TypeSet + ?Sized,
+{
+    type Item = OneOf<E>;
+    type IntoIter = std::vec::IntoIter<OneOf<E>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.errors.into_iter()
+    }
+}
+
+impl<'a, E> IntoIterator for &'a ErrorBag<E>
+where
+    E: TypeSet + ?Sized,
+{
+    type Item = &'a OneOf<E>;
+    type IntoIter = core::slice::Iter<'a, OneOf<E>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.errors.iter()
+    }
+}
+
+impl<E> fmt::Debug for ErrorBag<E>
+where
+    E: TypeSet + ?Sized,
+    OneOf<E>: fmt::Debug,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.debug_list().entries(&self.errors).finish()
+    }
+}
+
+/// Shows how many errors there are, and then each error on its own line.
+impl<E> fmt::Display for ErrorBag<E>
+where
+    E: TypeSet + ?Sized,
+    OneOf<E>: fmt::Display,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.errors.len() {
+            1 => write!(formatter, "1 error:")?,
+            len => write!(formatter, "{len} errors:")?,
+        }
+
+        for error in &self.errors {
+            write!(formatter, "\n  {error}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<E> Error for ErrorBag<E>
+where
+    E: TypeSet + ?Sized,
+    OneOf<E>: fmt::Debug + fmt::Display,
+{
+}
+
+/// Collects an iterator of `Result`s into either all of the successes,
+/// or an `ErrorBag` of all of the errors. Each error may be any of the
+/// types in the set or a `OneOf` whose variants are a subset of it.
+pub trait CollectErrors<T, E: TypeSet + ?Sized, Index> {
+    fn collect_errors(self) -> Result<Vec<T>, ErrorBag<E>>;
+}
+
+impl<I, T, X, E, Index> CollectErrors<T, E, Index> for I
+where
+    I: Iterator<Item = Result<T, X>>,
+    X: IntoOneOf<E, Index>,
+    E: TypeSet + ?Sized,
+{
+    fn collect_errors(self) -> Result<Vec<T>, ErrorBag<E>> {
+        let mut oks = vec![];
+        let mut errors = ErrorBag::new();
+
+        for result in self {
+            match result {
+                Ok(t) => oks.push(t),
+                Err(error) => errors.push(error),
+            }
+        }
+
+        errors.into_result(oks)
+    }
+}
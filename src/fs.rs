@@ -0,0 +1,103 @@
+//! Wrappers around the functions in `std::fs` which return a `OneOf`
+//! that only holds the kinds of failure each operation can
+//! realistically produce. Every error carries the path that was
+//! involved, which is shown when the error is displayed.
+//!
+//! ```
+//! use terrors::io::NotFound;
+//!
+//! let path = std::env::temp_dir().join("terrors-fs-doc-missing");
+//!
+//! match terrors::fs::read(&path).unwrap_err().narrow::<NotFound, _>() {
+//!     Ok(not_found) => assert_eq!(not_found.path(), Some(path.as_path())),
+//!     Err(_) => unreachable!(),
+//! }
+//! ```
+use std::fs::{self, File, Metadata, ReadDir};
+use std::path::Path;
+
+use crate::io::{
+    classify_with_path, AlreadyExists, CrossesDevices, IsADirectory, NotADirectory, NotFound,
+    OtherIo, PermissionDenied, StorageFull,
+};
+use crate::OneOf;
+
+/// The ways that `read` can fail.
+pub type ReadError = OneOf<(NotFound, PermissionDenied, IsADirectory, OtherIo)>;
+
+/// The ways that `write` can fail.
+pub type WriteError = OneOf<(
+    NotFound,
+    PermissionDenied,
+    IsADirectory,
+    StorageFull,
+    OtherIo,
+)>;
+
+/// The ways that `open` can fail.
+pub type OpenError = OneOf<(NotFound, PermissionDenied, OtherIo)>;
+
+/// The ways that `create_dir` can fail.
+pub type CreateDirError = OneOf<(NotFound, PermissionDenied, AlreadyExists, OtherIo)>;
+
+/// The ways that `rename` can fail.
+pub type RenameError = OneOf<(NotFound, PermissionDenied, CrossesDevices, OtherIo)>;
+
+/// The ways that `remove_file` can fail.
+pub type RemoveFileError = OneOf<(NotFound, PermissionDenied, IsADirectory, OtherIo)>;
+
+/// The ways that `metadata` can fail.
+pub type MetadataError = OneOf<(NotFound, PermissionDenied, OtherIo)>;
+
+/// The ways that `read_dir` can fail.
+pub type ReadDirError = OneOf<(NotFound, PermissionDenied, NotADirectory, OtherIo)>;
+
+/// Read the entire contents of a file into a bytes vector.
+pub fn read<P: AsRef<Path>>(path: P) -> Result<Vec<u8>, ReadError> {
+    let path = path.as_ref();
+    fs::read(path).map_err(|error| classify_with_path(error, path))
+}
+
+/// Write a slice as the entire contents of a file, creating
+/// it if it does not exist and truncating it if it does.
+pub fn write<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, contents: C) -> Result<(), WriteError> {
+    let path = path.as_ref();
+    fs::write(path, contents).map_err(|error| classify_with_path(error, path))
+}
+
+/// Open a file in read-only mode.
+pub fn open<P: AsRef<Path>>(path: P) -> Result<File, OpenError> {
+    let path = path.as_ref();
+    File::open(path).map_err(|error| classify_with_path(error, path))
+}
+
+/// Create a new, empty directory. The parent directory must already exist.
+pub fn create_dir<P: AsRef<Path>>(path: P) -> Result<(), CreateDirError> {
+    let path = path.as_ref();
+    fs::create_dir(path).map_err(|error| classify_with_path(error, path))
+}
+
+/// Rename a file or directory, replacing `to` if it already exists.
+/// Errors carry the `from` path.
+pub fn rename<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q) -> Result<(), RenameError> {
+    let from = from.as_ref();
+    fs::rename(from, to).map_err(|error| classify_with_path(error, from))
+}
+
+/// Remove a file. Directories must be removed with `std::fs::remove_dir`.
+pub fn remove_file<P: AsRef<Path>>(path: P) -> Result<(), RemoveFileError> {
+    let path = path.as_ref();
+    fs::remove_file(path).map_err(|error| classify_with_path(error, path))
+}
+
+/// Query the metadata of a file or directory, following symbolic links.
+pub fn metadata<P: AsRef<Path>>(path: P) -> Result<Metadata, MetadataError> {
+    let path = path.as_ref();
+    fs::metadata(path).map_err(|error| classify_with_path(error, path))
+}
+
+/// Return an iterator over the entries within a directory.
+pub fn read_dir<P: AsRef<Path>>(path: P) -> Result<ReadDir, ReadDirError> {
+    let path = path.as_ref();
+    fs::read_dir(path).map_err(|error| classify_with_path(error, path))
+}
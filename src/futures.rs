@@ -0,0 +1,205 @@
+//! Extension traits for futures and streams whose errors are a
+//! `OneOf`. This module requires the `futures` feature.
+//!
+//! ```
+//! use std::future::Future;
+//! use std::pin::pin;
+//! use std::task::{Context, Poll, Waker};
+//!
+//! use terrors::common::Timeout;
+//! use terrors::futures::OneOfFutureExt;
+//! use terrors::OneOf;
+//!
+//! #[derive(Debug)]
+//! struct NotEnoughMemory;
+//!
+//! let fut = async { Err::<u32, _>(OneOf::<(Timeout, NotEnoughMemory)>::new(Timeout::new())) };
+//! let fut = fut.handle_err(|_: Timeout| 0);
+//!
+//! let mut cx = Context::from_waker(Waker::noop());
+//! let Poll::Ready(result) = pin!(fut).poll(&mut cx) else {
+//!     unreachable!()
+//! };
+//! let result: Result<u32, OneOf<(NotEnoughMemory,)>> = result;
+//! assert_eq!(result.unwrap(), 0);
+//! ```
+use core::future::{poll_fn, Future};
+use core::pin::pin;
+use core::task::Poll;
+use core::time::Duration;
+
+use futures_core::Stream;
+
+use crate::common::Timeout;
+use crate::type_set::{Contains, Narrow, Replace, SupersetOf, TupleForm, TypeSet};
+use crate::OneOf;
+
+/// Methods for futures which return a `Result` with a `OneOf` error.
+#[allow(clippy::type_complexity)]
+pub trait OneOfFutureExt<T, E>: Future<Output = Result<T, OneOf<E>>> + Sized
+where
+    E: TypeSet + ?Sized,
+{
+    /// Turns the error into a `OneOf` with a set of variants
+    /// which is a superset of the current one.
+    fn broaden_err<Other, Index>(self) -> impl Future<Output = Result<T, OneOf<Other>>>
+    where
+        Other: TypeSet + ?Sized,
+        Other::Variants: SupersetOf<E::Variants, Index>,
+    {
+        async move { self.await.map_err(OneOf::broaden) }
+    }
+
+    /// Narrows the error to the `Target` type. The `Target` is returned
+    /// in the inner `Result` to be handled, and the remaining variants
+    /// in the outer `Result` so that they can be propagated with `?`.
+    fn narrow_err<Target, Index>(
+        self,
+    ) -> impl Future<
+        Output = Result<
+            Result<T, Target>,
+            OneOf<<<E::Variants as Narrow<Target, Index>>::Remainder as TupleForm>::Tuple>,
+        >,
+    >
+    where
+        E::Variants: Narrow<Target, Index>,
+    {
+        async move {
+            match self.await {
+                Ok(t) => Ok(Ok(t)),
+                Err(error) => error.narrow::<Target, Index>().map(Err),
+            }
+        }
+    }
+
+    /// Recovers from the `Target` variant using the `handler`,
+    /// which removes it from the error.
+    fn handle_err<Target, Index, H>(
+        self,
+        handler: H,
+    ) -> impl Future<
+        Output = Result<
+            T,
+            OneOf<<<E::Variants as Narrow<Target, Index>>::Remainder as TupleForm>::Tuple>,
+        >,
+    >
+    where
+        E::Variants: Narrow<Target, Index>,
+        H: FnOnce(Target) -> T,
+    {
+        async move {
+            match self.narrow_err::<Target, Index>().await? {
+                Ok(t) => Ok(t),
+                Err(target) => Ok(handler(target)),
+            }
+        }
+    }
+
+    /// Convert the `Target` variant of the error into a `Mapped` type.
+    /// See `OneOf::map_variant` for details.
+    fn map_variant<Target, Mapped, Index, F>(
+        self,
+        f: F,
+    ) -> impl Future<
+        Output = Result<
+            T,
+            OneOf<<<E::Variants as Replace<Target, Mapped, Index>>::Output as TupleForm>::Tuple>,
+        >,
+    >
+    where
+        E::Variants: Replace<Target, Mapped, Index>,
+        F: FnOnce(Target) -> Mapped,
+    {
+        async move { self.await.map_err(|error| error.map_variant(f)) }
+    }
+
+    /// Fail with a `Timeout` if the future does not complete before the
+    /// future returned by `sleep(duration)` does. This allows any runtime's
+    /// timer to be used, for example `fut.timeout(duration, tokio::time::sleep)`.
+    ///
+    /// The variants of the error are merged along with `Timeout` into the
+    /// `Other` set, which must be a superset of all of them. This may be the
+    /// same set as the error, for example when it already includes `Timeout`.
+    fn timeout<Other, Index, TimeoutIndex, S, Sleep>(
+        self,
+        duration: Duration,
+        sleep: S,
+    ) -> impl Future<Output = Result<T, OneOf<Other>>>
+    where
+        Other: TypeSet + ?Sized,
+        Other::Variants: SupersetOf<E::Variants, Index> + Contains<Timeout, TimeoutIndex>,
+        S: FnOnce(Duration) -> Sleep,
+        Sleep: Future<Output = ()>,
+    {
+        async move {
+            let mut future = pin!(self);
+            let mut sleep = pin!(sleep(duration));
+
+            poll_fn(|cx| {
+                if let Poll::Ready(result) = future.as_mut().poll(cx) {
+                    return Poll::Ready(result.map_err(OneOf::broaden));
+                }
+
+                match sleep.as_mut().poll(cx) {
+                    Poll::Ready(()) => Poll::Ready(Err(OneOf::new(Timeout::after(duration)))),
+                    Poll::Pending => Poll::Pending,
+                }
+            })
+            .await
+        }
+    }
+}
+
+impl<T, E, F> OneOfFutureExt<T, E> for F
+where
+    E: TypeSet + ?Sized,
+    F: Future<Output = Result<T, OneOf<E>>>,
+{
+}
+
+/// Methods for streams of `Result`s with a `OneOf` error.
+#[allow(clippy::type_complexity)]
+pub trait OneOfStreamExt<T, E>: Stream<Item = Result<T, OneOf<E>>> + Sized
+where
+    E: TypeSet + ?Sized,
+{
+    /// Drive the stream to completion, and partition its items into
+    /// the successes, the errors of the `Target` type, and the errors
+    /// of every other type.
+    fn partition_err<Target, Index>(
+        self,
+    ) -> impl Future<
+        Output = (
+            Vec<T>,
+            Vec<Target>,
+            Vec<OneOf<<<E::Variants as Narrow<Target, Index>>::Remainder as TupleForm>::Tuple>>,
+        ),
+    >
+    where
+        E::Variants: Narrow<Target, Index>,
+    {
+        async move {
+            let mut stream = pin!(self);
+            let mut oks = vec![];
+            let mut targets = vec![];
+            let mut rest = vec![];
+
+            while let Some(item) = poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+                match item.map_err(OneOf::narrow::<Target, Index>) {
+                    Ok(t) => oks.push(t),
+                    Err(Ok(target)) => targets.push(target),
+                    Err(Err(remainder)) => rest.push(remainder),
+                }
+            }
+
+            (oks, targets, rest)
+        }
+    }
+}
+
+impl<T, E, S> OneOfStreamExt<T, E> for S
+where
+    E: TypeSet + ?Sized,
+    S: Stream<Item = Result<T, OneOf<E>>>,
+{
+}
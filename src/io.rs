@@ -0,0 +1,308 @@
+//! Error types for each `std::io::ErrorKind`, which allow an
+//! `io::Error` to be split into a `OneOf` that can be narrowed
+//! on the kind of failure. Some of these kinds were stabilized
+//! recently, so this module and the `fs` and `net` wrappers are
+//! behind the `io` feature, which requires Rust 1.87.
+//!
+//! ```
+//! use std::io;
+//! use terrors::io::{classify, NotFound};
+//!
+//! let error = io::Error::new(io::ErrorKind::NotFound, "config.toml");
+//!
+//! match classify(error).narrow::<NotFound, _>() {
+//!     Ok(not_found) => assert_eq!(not_found.to_string(), "config.toml"),
+//!     Err(_) => unreachable!(),
+//! }
+//! ```
+use core::fmt;
+use std::error::Error;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+
+use crate::type_set::{Contains, TypeSet};
+use crate::{Cons, End, OneOf};
+
+/// Implemented by each of the error types in this module
+/// to create them from an `io::Error` of the matching kind.
+pub trait FromIoError: Sized {
+    /// Returns `true` if an error of this `kind` belongs in this type.
+    fn matches(kind: ErrorKind) -> bool;
+
+    /// Wrap the `io::Error`, which should be of a matching kind,
+    /// along with the path that was involved, if there is one.
+    fn from_io_error(error: io::Error, path: Option<PathBuf>) -> Self;
+}
+
+macro_rules! io_error_common {
+    ($name:ident) => {
+        impl $name {
+            /// Borrow the original `io::Error`.
+            pub fn as_io_error(&self) -> &io::Error {
+                &self.error
+            }
+
+            /// Return the original `io::Error`.
+            pub fn into_io_error(self) -> io::Error {
+                self.error
+            }
+
+            /// Returns the path that was involved, if there is one.
+            pub fn path(&self) -> Option<&Path> {
+                self.path.as_deref()
+            }
+        }
+
+        impl From<$name> for io::Error {
+            fn from(error: $name) -> io::Error {
+                error.error
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter
+                    .debug_struct(stringify!($name))
+                    .field("error", &self.error)
+                    .field("path", &self.path)
+                    .finish()
+            }
+        }
+
+        /// The path is shown before the error, if there is one.
+        impl fmt::Display for $name {
+            fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match &self.path {
+                    Some(path) => write!(formatter, "{}: {}", path.display(), self.error),
+                    None => self.error.fmt(formatter),
+                }
+            }
+        }
+
+        /// The error wrapped by the `io::Error` is its source, because
+        /// `io::Error::source` skips over it to that error's own source.
+        impl Error for $name {
+            fn source(&self) -> Option<&(dyn Error + 'static)> {
+                match self.error.get_ref() {
+                    Some(inner) => Some(inner),
+                    None => self.error.source(),
+                }
+            }
+        }
+    };
+}
+
+macro_rules! io_error_kinds {
+    ($($(#[$meta:meta])* $name:ident,)*) => {
+        $(
+            $(#[$meta])*
+            pub struct $name {
+                error: io::Error,
+                path: Option<PathBuf>,
+            }
+
+            impl FromIoError for $name {
+                fn matches(kind: ErrorKind) -> bool {
+                    kind == ErrorKind::$name
+                }
+
+                fn from_io_error(error: io::Error, path: Option<PathBuf>) -> $name {
+                    $name { error, path }
+                }
+            }
+
+            io_error_common!($name);
+        )*
+    };
+}
+
+io_error_kinds! {
+    /// An `io::Error` of kind `ErrorKind::NotFound`.
+    NotFound,
+    /// An `io::Error` of kind `ErrorKind::PermissionDenied`.
+    PermissionDenied,
+    /// An `io::Error` of kind `ErrorKind::ConnectionRefused`.
+    ConnectionRefused,
+    /// An `io::Error` of kind `ErrorKind::ConnectionReset`.
+    ConnectionReset,
+    /// An `io::Error` of kind `ErrorKind::HostUnreachable`.
+    HostUnreachable,
+    /// An `io::Error` of kind `ErrorKind::NetworkUnreachable`.
+    NetworkUnreachable,
+    /// An `io::Error` of kind `ErrorKind::ConnectionAborted`.
+    ConnectionAborted,
+    /// An `io::Error` of kind `ErrorKind::NotConnected`.
+    NotConnected,
+    /// An `io::Error` of kind `ErrorKind::AddrInUse`.
+    AddrInUse,
+    /// An `io::Error` of kind `ErrorKind::AddrNotAvailable`.
+    AddrNotAvailable,
+    /// An `io::Error` of kind `ErrorKind::NetworkDown`.
+    NetworkDown,
+    /// An `io::Error` of kind `ErrorKind::BrokenPipe`.
+    BrokenPipe,
+    /// An `io::Error` of kind `ErrorKind::AlreadyExists`.
+    AlreadyExists,
+    /// An `io::Error` of kind `ErrorKind::WouldBlock`.
+    WouldBlock,
+    /// An `io::Error` of kind `ErrorKind::NotADirectory`.
+    NotADirectory,
+    /// An `io::Error` of kind `ErrorKind::IsADirectory`.
+    IsADirectory,
+    /// An `io::Error` of kind `ErrorKind::DirectoryNotEmpty`.
+    DirectoryNotEmpty,
+    /// An `io::Error` of kind `ErrorKind::ReadOnlyFilesystem`.
+    ReadOnlyFilesystem,
+    /// An `io::Error` of kind `ErrorKind::StaleNetworkFileHandle`.
+    StaleNetworkFileHandle,
+    /// An `io::Error` of kind `ErrorKind::InvalidInput`.
+    InvalidInput,
+    /// An `io::Error` of kind `ErrorKind::InvalidData`.
+    InvalidData,
+    /// An `io::Error` of kind `ErrorKind::TimedOut`.
+    TimedOut,
+    /// An `io::Error` of kind `ErrorKind::WriteZero`.
+    WriteZero,
+    /// An `io::Error` of kind `ErrorKind::StorageFull`.
+    StorageFull,
+    /// An `io::Error` of kind `ErrorKind::NotSeekable`.
+    NotSeekable,
+    /// An `io::Error` of kind `ErrorKind::QuotaExceeded`.
+    QuotaExceeded,
+    /// An `io::Error` of kind `ErrorKind::FileTooLarge`.
+    FileTooLarge,
+    /// An `io::Error` of kind `ErrorKind::ResourceBusy`.
+    ResourceBusy,
+    /// An `io::Error` of kind `ErrorKind::ExecutableFileBusy`.
+    ExecutableFileBusy,
+    /// An `io::Error` of kind `ErrorKind::Deadlock`.
+    Deadlock,
+    /// An `io::Error` of kind `ErrorKind::CrossesDevices`.
+    CrossesDevices,
+    /// An `io::Error` of kind `ErrorKind::TooManyLinks`.
+    TooManyLinks,
+    /// An `io::Error` of kind `ErrorKind::InvalidFilename`.
+    InvalidFilename,
+    /// An `io::Error` of kind `ErrorKind::ArgumentListTooLong`.
+    ArgumentListTooLong,
+    /// An `io::Error` of kind `ErrorKind::Interrupted`.
+    Interrupted,
+    /// An `io::Error` of kind `ErrorKind::Unsupported`.
+    Unsupported,
+    /// An `io::Error` of kind `ErrorKind::UnexpectedEof`.
+    UnexpectedEof,
+    /// An `io::Error` of kind `ErrorKind::OutOfMemory`.
+    OutOfMemory,
+}
+
+/// An `io::Error` of any kind which does not have its own type
+/// in a set, including `ErrorKind::Other`. This is used as the
+/// fallback when classifying an `io::Error`.
+pub struct OtherIo {
+    error: io::Error,
+    path: Option<PathBuf>,
+}
+
+impl OtherIo {
+    /// Returns the kind of the original `io::Error`.
+    pub fn kind(&self) -> ErrorKind {
+        self.error.kind()
+    }
+}
+
+/// `OtherIo` is never matched by kind, and only
+/// receives the errors that no other type matched.
+impl FromIoError for OtherIo {
+    fn matches(_: ErrorKind) -> bool {
+        false
+    }
+
+    fn from_io_error(error: io::Error, path: Option<PathBuf>) -> OtherIo {
+        OtherIo { error, path }
+    }
+}
+
+io_error_common!(OtherIo);
+
+/* ------------------------- Classification ----------------------- */
+
+#[doc(hidden)]
+pub trait ClassifyFold: Sized {
+    fn classify_fold(
+        error: io::Error,
+        path: Option<PathBuf>,
+    ) -> Result<Self, (io::Error, Option<PathBuf>)>;
+}
+
+impl ClassifyFold for End {
+    fn classify_fold(
+        error: io::Error,
+        path: Option<PathBuf>,
+    ) -> Result<End, (io::Error, Option<PathBuf>)> {
+        Err((error, path))
+    }
+}
+
+impl<Head, Tail> ClassifyFold for Cons<Head, Tail>
+where
+    Head: FromIoError,
+    Tail: ClassifyFold,
+{
+    fn classify_fold(
+        error: io::Error,
+        path: Option<PathBuf>,
+    ) -> Result<Self, (io::Error, Option<PathBuf>)> {
+        if Head::matches(error.kind()) {
+            Ok(Cons::Head(Box::new(Head::from_io_error(error, path))))
+        } else {
+            Tail::classify_fold(error, path).map(Cons::Tail)
+        }
+    }
+}
+
+/// Split an `io::Error` into the most common kinds of failure.
+pub fn classify(
+    error: io::Error,
+) -> OneOf<(
+    NotFound,
+    PermissionDenied,
+    AlreadyExists,
+    TimedOut,
+    Interrupted,
+    WouldBlock,
+    OtherIo,
+)> {
+    classify_into(error)
+}
+
+/// Split an `io::Error` into any set of the error types in this
+/// module, which must include `OtherIo` to receive the errors of
+/// every kind that is not in the set.
+pub fn classify_into<E, Index>(error: io::Error) -> OneOf<E>
+where
+    E: TypeSet + ?Sized,
+    E::Variants: ClassifyFold + Contains<OtherIo, Index>,
+{
+    classify_path(error, None)
+}
+
+/// The same as `classify_into`, but the error also carries
+/// the path of the file or directory that was involved.
+pub fn classify_with_path<E, Index>(error: io::Error, path: impl Into<PathBuf>) -> OneOf<E>
+where
+    E: TypeSet + ?Sized,
+    E::Variants: ClassifyFold + Contains<OtherIo, Index>,
+{
+    classify_path(error, Some(path.into()))
+}
+
+fn classify_path<E, Index>(error: io::Error, path: Option<PathBuf>) -> OneOf<E>
+where
+    E: TypeSet + ?Sized,
+    E::Variants: ClassifyFold + Contains<OtherIo, Index>,
+{
+    match E::Variants::classify_fold(error, path) {
+        Ok(value) => OneOf { value },
+        Err((error, path)) => OneOf::new(OtherIo::from_io_error(error, path)),
+    }
+}
@@ -0,0 +1,202 @@
+//! Run several fallible futures concurrently, converting each of their
+//! errors into one shared `OneOf`. Each future may fail with a plain
+//! error type or with a `OneOf`, as long as the `Other` set that is
+//! returned contains all of their error types. As with `broaden`, the
+//! `Other` set is usually inferred from the function's return type.
+//!
+//! ```
+//! use std::future::{ready, Future};
+//! use std::pin::pin;
+//! use std::task::{Context, Poll, Waker};
+//!
+//! use terrors::join::try_join3;
+//! use terrors::OneOf;
+//!
+//! #[derive(Debug)]
+//! struct A;
+//! #[derive(Debug)]
+//! struct B;
+//! #[derive(Debug)]
+//! struct C;
+//! #[derive(Debug)]
+//! struct D;
+//!
+//! async fn load() -> Result<(u8, u16, u32), OneOf<(A, B, C, D)>> {
+//!     try_join3(
+//!         ready(Ok::<_, OneOf<(A, B)>>(1)),
+//!         ready(Err::<u16, _>(OneOf::<(C,)>::new(C))),
+//!         ready(Ok::<_, D>(3)),
+//!     )
+//!     .await
+//! }
+//!
+//! let mut cx = Context::from_waker(Waker::noop());
+//! let Poll::Ready(result) = pin!(load()).poll(&mut cx) else {
+//!     unreachable!()
+//! };
+//! assert!(result.unwrap_err().narrow::<C, _>().is_ok());
+//! ```
+use core::future::{poll_fn, Future};
+use core::pin::pin;
+use core::task::Poll;
+
+use crate::type_set::TypeSet;
+use crate::{IntoOneOf, OneOf};
+
+macro_rules! try_joins {
+    ($($try_join:ident, $settle:ident, $n:literal => ($($f:ident: $F:ident, $T:ident, $E:ident, $I:ident;)+))*) => {
+        $(
+            #[doc = concat!("Run ", $n, " futures concurrently until they have all succeeded, or")]
+            /// until one of them fails. The first error is returned, and the
+            /// other futures are dropped without being polled to completion.
+            #[allow(clippy::too_many_arguments)]
+            pub async fn $try_join<Other, $($F, $T, $E, $I),+>(
+                $($f: $F),+
+            ) -> Result<($($T,)+), OneOf<Other>>
+            where
+                Other: TypeSet + ?Sized,
+                $(
+                    $F: Future<Output = Result<$T, $E>>,
+                    $E: IntoOneOf<Other, $I>,
+                )+
+            {
+                $(let mut $f = (pin!($f), None);)+
+
+                poll_fn(|cx| {
+                    $(
+                        if $f.1.is_none() {
+                            if let Poll::Ready(result) = $f.0.as_mut().poll(cx) {
+                                match result {
+                                    Ok(t) => $f.1 = Some(t),
+                                    Err(error) => return Poll::Ready(Err(error.into_one_of())),
+                                }
+                            }
+                        }
+                    )+
+
+                    if $($f.1.is_some())&&+ {
+                        Poll::Ready(Ok(($($f.1.take().unwrap(),)+)))
+                    } else {
+                        Poll::Pending
+                    }
+                })
+                .await
+            }
+
+            #[doc = concat!("Run ", $n, " futures concurrently until they have all finished.")]
+            /// Unlike `try_join`, a failure does not cancel the other futures,
+            /// and the errors of every future that failed are returned, in the
+            /// order that the futures were passed in.
+            #[allow(clippy::too_many_arguments)]
+            pub async fn $settle<Other, $($F, $T, $E, $I),+>(
+                $($f: $F),+
+            ) -> Result<($($T,)+), Vec<OneOf<Other>>>
+            where
+                Other: TypeSet + ?Sized,
+                $(
+                    $F: Future<Output = Result<$T, $E>>,
+                    $E: IntoOneOf<Other, $I>,
+                )+
+            {
+                $(let mut $f = (pin!($f), None);)+
+
+                poll_fn(|cx| {
+                    $(
+                        if $f.1.is_none() {
+                            if let Poll::Ready(result) = $f.0.as_mut().poll(cx) {
+                                $f.1 = Some(result);
+                            }
+                        }
+                    )+
+
+                    if $($f.1.is_some())&&+ {
+                        Poll::Ready(())
+                    } else {
+                        Poll::Pending
+                    }
+                })
+                .await;
+
+                let mut errors = vec![];
+
+                $(
+                    let $f = match $f.1.take().unwrap() {
+                        Ok(t) => Some(t),
+                        Err(error) => {
+                            errors.push(error.into_one_of());
+                            None
+                        }
+                    };
+                )+
+
+                match ($($f,)+) {
+                    ($(Some($f),)+) => Ok(($($f,)+)),
+                    _ => Err(errors),
+                }
+            }
+        )*
+    };
+}
+
+try_joins! {
+    try_join2, settle2, "two" => (
+        fa: FA, TA, EA, IA;
+        fb: FB, TB, EB, IB;
+    )
+    try_join3, settle3, "three" => (
+        fa: FA, TA, EA, IA;
+        fb: FB, TB, EB, IB;
+        fc: FC, TC, EC, IC;
+    )
+    try_join4, settle4, "four" => (
+        fa: FA, TA, EA, IA;
+        fb: FB, TB, EB, IB;
+        fc: FC, TC, EC, IC;
+        fd: FD, TD, ED, ID;
+    )
+    try_join5, settle5, "five" => (
+        fa: FA, TA, EA, IA;
+        fb: FB, TB, EB, IB;
+        fc: FC, TC, EC, IC;
+        fd: FD, TD, ED, ID;
+        fe: FE, TE, EE, IE;
+    )
+    try_join6, settle6, "six" => (
+        fa: FA, TA, EA, IA;
+        fb: FB, TB, EB, IB;
+        fc: FC, TC, EC, IC;
+        fd: FD, TD, ED, ID;
+        fe: FE, TE, EE, IE;
+        ff: FF, TF, EF, IF;
+    )
+    try_join7, settle7, "seven" => (
+        fa: FA, TA, EA, IA;
+        fb: FB, TB, EB, IB;
+        fc: FC, TC, EC, IC;
+        fd: FD, TD, ED, ID;
+        fe: FE, TE, EE, IE;
+        ff: FF, TF, EF, IF;
+        fg: FG, TG, EG, IG;
+    )
+    try_join8, settle8, "eight" => (
+        fa: FA, TA, EA, IA;
+        fb: FB, TB, EB, IB;
+        fc: FC, TC, EC, IC;
+        fd: FD, TD, ED, ID;
+        fe: FE, TE, EE, IE;
+        ff: FF, TF, EF, IF;
+        fg: FG, TG, EG, IG;
+        fh: FH, TH, EH, IH;
+    )
+    try_join9, settle9, "nine" => (
+        fa: FA, TA, EA, IA;
+        fb: FB, TB, EB, IB;
+        fc: FC, TC, EC, IC;
+        fd: FD, TD, ED, ID;
+        fe: FE, TE, EE, IE;
+        ff: FF, TF, EF, IF;
+        fg: FG, TG, EG, IG;
+        fh: FH, TH, EH, IH;
+        fi: FI, TI, EI, II;
+    )
+}
@@ -8,14 +8,58 @@
 #[cfg(doctest)]
 pub struct ReadmeDoctests;
 
+/// A `OneOf` is only `Send` when all of its variants are:
+///
+/// ```compile_fail
+/// fn is_send<T: Send>() {}
+///
+/// is_send::<terrors::OneOf<(std::rc::Rc<u8>,)>>();
+/// ```
+///
+/// and only `Sync` when all of its variants are:
+///
+/// ```compile_fail
+/// fn is_sync<T: Sync>() {}
+///
+/// is_sync::<terrors::OneOf<(std::rc::Rc<u8>,)>>();
+/// ```
+#[cfg(doctest)]
+pub struct AutoTraitDoctests;
+
+pub mod circuit;
+pub mod common;
+#[cfg(feature = "io")]
+pub mod fs;
+#[cfg(feature = "futures")]
+pub mod futures;
+#[cfg(feature = "io")]
+pub mod io;
+pub mod join;
+#[cfg(feature = "io")]
+pub mod net;
+pub mod retry;
+
+mod error_bag;
 mod one_of;
 mod one_of_to_enum;
+mod other;
+mod shared;
+mod suppressed;
 mod type_set;
+mod visit;
 
 /// Similar to anonymous unions / enums in languages that support type narrowing.
 pub use one_of::OneOf;
 
-pub use type_set::{TypeSet, E1, E2, E3, E4, E5, E6, E7, E8, E9};
+pub use error_bag::{CollectErrors, ErrorBag};
+pub use one_of::{DisplayWithType, FlattenErr, IntoOneOf, UnwrapInfallible};
+pub use other::Other;
+pub use shared::SharedOneOf;
+pub use suppressed::WithSuppressed;
+
+pub use visit::{Projection, Visitor, VisitorMut, VisitorOwned};
+
+pub use type_set::{TypeSet, E0, E1, E2, E3, E4, E5, E6, E7, E8, E9};
 
 /* ------------------------- Helpers ----------------------- */
 
@@ -27,10 +71,29 @@ pub enum End {}
 impl std::error::Error for End {}
 
 /// A compile-time list of types, similar to other basic functional list structures.
+/// A value of this type holds exactly one boxed element of the list, which is either
+/// the `Head` or, recursively, one of the types in the `Tail`.
 #[doc(hidden)]
-#[derive(Debug)]
-pub struct Cons<Head, Tail>(core::marker::PhantomData<Head>, Tail);
+pub enum Cons<Head: ?Sized, Tail> {
+    Head(Box<Head>),
+    Tail(Tail),
+}
 
 #[doc(hidden)]
 #[derive(Debug)]
 pub struct Recurse<Tail>(Tail);
+
+/// Index marker for placing a value directly into a `OneOf`.
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct Placed<Index>(Index);
+
+/// Index marker for broadening a nested `OneOf` into another `OneOf`.
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct Broadened<Index>(Index);
+
+/// Index marker for descending into a nested `OneOf`.
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct Nested<Index>(Index);
@@ -0,0 +1,103 @@
+//! Wrappers around `std::net` which return a `OneOf` that only holds
+//! the kinds of failure each operation can realistically produce, so
+//! that retry code can `narrow` out the transient ones.
+//!
+//! ```
+//! use std::time::Duration;
+//!
+//! use terrors::io::ConnectionRefused;
+//!
+//! let listener = terrors::net::bind("127.0.0.1:0").unwrap();
+//! let address = listener.local_addr().unwrap();
+//! drop(listener);
+//!
+//! match terrors::net::connect_timeout(&address, Duration::from_secs(1)) {
+//!     Ok(_) => {}
+//!     Err(error) => assert!(error.narrow::<ConnectionRefused, _>().is_ok()),
+//! }
+//! ```
+use std::io::{self, ErrorKind, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs, UdpSocket};
+use std::time::Duration;
+
+use crate::io::{
+    classify_into, AddrInUse, AddrNotAvailable, BrokenPipe, ConnectionAborted, ConnectionRefused,
+    ConnectionReset, HostUnreachable, NetworkUnreachable, OtherIo, PermissionDenied, TimedOut,
+};
+use crate::OneOf;
+
+/// The ways that `connect` and `connect_timeout` can fail.
+pub type ConnectError = OneOf<(
+    ConnectionRefused,
+    TimedOut,
+    HostUnreachable,
+    NetworkUnreachable,
+    OtherIo,
+)>;
+
+/// The ways that `bind` and `bind_udp` can fail.
+pub type BindError = OneOf<(AddrInUse, AddrNotAvailable, PermissionDenied, OtherIo)>;
+
+/// The ways that `read_timeout` can fail.
+pub type ReadError = OneOf<(TimedOut, ConnectionReset, ConnectionAborted, OtherIo)>;
+
+/// The ways that `write_all_timeout` can fail.
+pub type WriteError = OneOf<(TimedOut, ConnectionReset, BrokenPipe, OtherIo)>;
+
+/// Open a TCP connection to a remote host.
+pub fn connect<A: ToSocketAddrs>(address: A) -> Result<TcpStream, ConnectError> {
+    TcpStream::connect(address).map_err(classify_into)
+}
+
+/// Open a TCP connection to a remote host, failing with
+/// `TimedOut` if it is not established within `timeout`.
+pub fn connect_timeout(address: &SocketAddr, timeout: Duration) -> Result<TcpStream, ConnectError> {
+    TcpStream::connect_timeout(address, timeout).map_err(classify_into)
+}
+
+/// Create a TCP listener bound to the given address.
+pub fn bind<A: ToSocketAddrs>(address: A) -> Result<TcpListener, BindError> {
+    TcpListener::bind(address).map_err(classify_into)
+}
+
+/// Create a UDP socket bound to the given address.
+pub fn bind_udp<A: ToSocketAddrs>(address: A) -> Result<UdpSocket, BindError> {
+    UdpSocket::bind(address).map_err(classify_into)
+}
+
+/// Read some bytes from the stream, failing with `TimedOut` if none
+/// arrive within `timeout`. This sets the read timeout of the stream.
+pub fn read_timeout(
+    mut stream: &TcpStream,
+    buf: &mut [u8],
+    timeout: Duration,
+) -> Result<usize, ReadError> {
+    stream
+        .set_read_timeout(Some(timeout))
+        .and_then(|()| stream.read(buf))
+        .map_err(|error| classify_into(timed_out(error)))
+}
+
+/// Write the whole buffer to the stream, failing with `TimedOut` if it
+/// stalls for longer than `timeout`. This sets the write timeout of the
+/// stream.
+pub fn write_all_timeout(
+    mut stream: &TcpStream,
+    buf: &[u8],
+    timeout: Duration,
+) -> Result<(), WriteError> {
+    stream
+        .set_write_timeout(Some(timeout))
+        .and_then(|()| stream.write_all(buf))
+        .map_err(|error| classify_into(timed_out(error)))
+}
+
+/// Depending on the platform, an elapsed socket timeout is reported
+/// as either `TimedOut` or `WouldBlock`. This settles on `TimedOut`.
+fn timed_out(error: io::Error) -> io::Error {
+    if error.kind() == ErrorKind::WouldBlock {
+        io::Error::new(ErrorKind::TimedOut, error)
+    } else {
+        error
+    }
+}
@@ -1,15 +1,21 @@
 use core::any::Any;
+use core::cell::Cell;
+use core::convert::Infallible;
 use core::fmt;
-use core::marker::PhantomData;
+use core::future::Future;
 use core::ops::Deref;
+use core::pin::Pin;
+use core::task::{Context, Poll};
 use std::error::Error;
+use std::io;
 
 use crate::type_set::{
-    CloneFold, Contains, DebugFold, DisplayFold, ErrorFold, IsFold, Narrow, SupersetOf, TupleForm,
-    TypeSet,
+    AnyFold, Contains, ErrorFold, FlattenFold, FromErrorFold, IntoErrorFold, Lift, Narrow,
+    NarrowDeep, ProjectFold, Replace, ShortTypeName, Split, SupersetOf, TupleForm, TypeNameFold,
+    TypeSet, VisitFold, VisitMutFold, VisitOwnedFold,
 };
 
-use crate::{Cons, End};
+use crate::{Broadened, Cons, End, Placed};
 
 /* ------------------------- OneOf ----------------------- */
 
@@ -29,9 +35,15 @@ use crate::{Cons, End};
 /// you to quickly specify a function's return value as
 /// involving a precise subset of errors that the caller
 /// can clearly reason about.
-pub struct OneOf<E: TypeSet> {
-    pub(crate) value: Box<dyn Any>,
-    _pd: PhantomData<E>,
+///
+/// The active variant is stored in a typed recursive sum
+/// rather than behind `dyn Any`, so a `OneOf` is `Send`
+/// or `Sync` exactly when all of its variants are.
+/// Variants may also borrow data, such as a parse error that
+/// points into its input. Only the methods which convert to or
+/// from `dyn Any` or `dyn Error` require `'static` variants.
+pub struct OneOf<E: TypeSet + ?Sized> {
+    pub(crate) value: E::Variants,
 }
 
 fn _send_sync_error_assert() {
@@ -41,98 +53,278 @@ fn _send_sync_error_assert() {
     fn is_sync<T: Sync>(_: &T) {}
     fn is_error<T: Error>(_: &T) {}
 
-    let o: OneOf<(io::Error,)> = OneOf::new(io::Error::new(io::ErrorKind::Other, "yooo"));
+    let o: OneOf<(io::Error,)> = OneOf::new(io::Error::other("yooo"));
     is_send(&o);
     is_sync(&o);
     is_error(&o);
 }
 
-unsafe impl<T> Send for OneOf<T> where T: TypeSet + Send {}
-unsafe impl<T> Sync for OneOf<T> where T: TypeSet + Sync {}
-
-impl<T> Deref for OneOf<(T,)>
-where
-    T: 'static,
-{
+impl<T: ?Sized + Lift> Deref for OneOf<(T,)> {
     type Target = T;
 
     fn deref(&self) -> &T {
-        self.value.downcast_ref::<T>().unwrap()
+        match &self.value {
+            Cons::Head(t) => t,
+            Cons::Tail(end) => match *end {},
+        }
     }
 }
 
-impl<T> From<T> for OneOf<(T,)>
-where
-    T: 'static,
-{
+impl<T> From<T> for OneOf<(T,)> {
     fn from(t: T) -> OneOf<(T,)> {
         OneOf::new(t)
     }
 }
 
+impl From<Infallible> for OneOf<()> {
+    fn from(infallible: Infallible) -> OneOf<()> {
+        match infallible {}
+    }
+}
+
+impl From<OneOf<()>> for Infallible {
+    fn from(one_of: OneOf<()>) -> Infallible {
+        one_of.unreachable()
+    }
+}
+
 impl<E> Clone for OneOf<E>
 where
-    E: TypeSet,
-    E::Variants: Clone + CloneFold,
+    E: TypeSet + ?Sized,
+    E::Variants: Clone,
 {
     fn clone(&self) -> Self {
-        let value = E::Variants::clone_fold(&self.value);
-
         OneOf {
-            value,
-            _pd: PhantomData,
+            value: self.value.clone(),
         }
     }
 }
+
+/// Only the active variant is shown when debug printed, but
+/// the alternate form `{:#?}` also shows the set and the type
+/// of the active variant, such as `OneOf<(u32, String)>::u32(5)`.
 impl<E> fmt::Debug for OneOf<E>
 where
-    E: TypeSet,
-    E::Variants: fmt::Debug + DebugFold,
+    E: TypeSet + ?Sized,
+    E::Variants: fmt::Debug,
 {
     fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
-        E::Variants::debug_fold(&self.value, formatter)
+        if !formatter.alternate() {
+            return self.value.fmt(formatter);
+        }
+
+        write!(
+            formatter,
+            "OneOf<{}>::{}(",
+            ShortTypeName(core::any::type_name::<E>()),
+            ShortTypeName(self.value.type_name_fold()),
+        )?;
+        self.value.fmt(formatter)?;
+        formatter.write_str(")")
     }
 }
 
 impl<E> fmt::Display for OneOf<E>
 where
-    E: TypeSet,
-    E::Variants: fmt::Display + DisplayFold,
+    E: TypeSet + ?Sized,
+    E::Variants: fmt::Display,
 {
     fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
-        E::Variants::display_fold(&self.value, formatter)
+        self.value.fmt(formatter)
+    }
+}
+
+thread_local! {
+    /// Set by `find_source` while it asks a link in a chain of sources
+    /// for its source, so that a `OneOf` link returns its active variant
+    /// instead of skipping over it. Only the first `OneOf` asked sees it.
+    static STEP_INTO: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Clears `STEP_INTO` when dropped, even if the link's `source` panics.
+struct StepInto;
+
+impl StepInto {
+    fn source<'a>(error: &'a (dyn Error + 'static)) -> Option<&'a (dyn Error + 'static)> {
+        STEP_INTO.with(|step_into| step_into.set(true));
+        let _guard = StepInto;
+        error.source()
+    }
+}
+
+impl Drop for StepInto {
+    fn drop(&mut self) {
+        STEP_INTO.with(|step_into| step_into.set(false));
     }
 }
 
 impl<E> Error for OneOf<E>
 where
-    E: TypeSet,
-    E::Variants: Error + DebugFold + DisplayFold + ErrorFold,
+    E: TypeSet + ?Sized,
+    E::Variants: Error + ErrorFold,
 {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
-        E::Variants::source_fold(&self.value)
+        if STEP_INTO.with(|step_into| step_into.replace(false)) {
+            return Some(self.value.error_fold());
+        }
+        self.value.source()
+    }
+
+    #[cfg(feature = "error_provide")]
+    fn provide<'a>(&'a self, request: &mut std::error::Request<'a>) {
+        self.value.provide(request)
+    }
+}
+
+/// The active variant is always boxed and never pinned in place,
+/// so a `OneOf` can be moved even after it has been pinned.
+impl<E> Unpin for OneOf<E> where E: TypeSet + ?Sized {}
+
+impl<E> Iterator for OneOf<E>
+where
+    E: TypeSet + ?Sized,
+    E::Variants: Iterator,
+{
+    type Item = <E::Variants as Iterator>::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.value.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.value.size_hint()
+    }
+}
+
+/// A `OneOf` of futures can be polled if every variant is `Unpin`,
+/// which can be achieved by pinning `!Unpin` futures in a `Box`.
+impl<E> Future for OneOf<E>
+where
+    E: TypeSet + ?Sized,
+    E::Variants: Future + Unpin,
+{
+    type Output = <E::Variants as Future>::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.get_mut().value).poll(cx)
+    }
+}
+
+impl<E> io::Read for OneOf<E>
+where
+    E: TypeSet + ?Sized,
+    E::Variants: io::Read,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.value.read(buf)
+    }
+}
+
+impl<E> io::BufRead for OneOf<E>
+where
+    E: TypeSet + ?Sized,
+    E::Variants: io::BufRead,
+{
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.value.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.value.consume(amt)
+    }
+}
+
+impl<E> io::Write for OneOf<E>
+where
+    E: TypeSet + ?Sized,
+    E::Variants: io::Write,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.value.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.value.flush()
+    }
+}
+
+impl<E> AsRef<str> for OneOf<E>
+where
+    E: TypeSet + ?Sized,
+    E::Variants: AsRef<str>,
+{
+    fn as_ref(&self) -> &str {
+        self.value.as_ref()
     }
 }
 
 impl<E> OneOf<E>
 where
-    E: TypeSet,
+    E: TypeSet + ?Sized,
 {
     /// Create a new `OneOf`.
     pub fn new<T, Index>(t: T) -> OneOf<E>
     where
-        T: Any,
+        E::Variants: Contains<T, Index>,
+    {
+        OneOf::from_box(Box::new(t))
+    }
+
+    /// Create a new `OneOf` from a boxed value without
+    /// moving it out of its existing allocation. This is
+    /// also how unsized variants such as `str` or `dyn Error`
+    /// are created.
+    pub fn from_box<T, Index>(t: Box<T>) -> OneOf<E>
+    where
+        T: ?Sized,
         E::Variants: Contains<T, Index>,
     {
         OneOf {
-            value: Box::new(t),
-            _pd: PhantomData,
+            value: <E::Variants as Contains<T, Index>>::inject(t),
         }
     }
 
+    /// Attempt to adopt a type-erased value into a `OneOf`,
+    /// returning the value unchanged if its type is not one
+    /// of the possible variants. The existing allocation is
+    /// reused.
+    pub fn try_from_any(value: Box<dyn Any>) -> Result<OneOf<E>, Box<dyn Any>>
+    where
+        E::Variants: AnyFold,
+    {
+        let value = E::Variants::from_any_fold(value)?;
+
+        Ok(OneOf { value })
+    }
+
+    /// Attempt to adopt a type-erased error into a `OneOf`,
+    /// returning the error unchanged if its type is not one
+    /// of the possible variants. The existing allocation is
+    /// reused.
+    pub fn try_from_dyn_error(
+        error: Box<dyn Error + Send + Sync>,
+    ) -> Result<OneOf<E>, Box<dyn Error + Send + Sync>>
+    where
+        E::Variants: FromErrorFold,
+    {
+        let value = E::Variants::from_error_fold(error)?;
+
+        Ok(OneOf { value })
+    }
+
+    /// Convert the `OneOf` into the type-erased active variant,
+    /// which can be downcast to its concrete type.
+    pub fn into_any(self) -> Box<dyn Any>
+    where
+        E::Variants: AnyFold,
+    {
+        self.value.into_any_fold()
+    }
+
     /// Attempt to downcast the `OneOf` into a specific type, and
     /// if that fails, return a `OneOf` which does not contain that
     /// type as one of its possible variants.
+    #[allow(clippy::type_complexity)]
     pub fn narrow<Target, Index>(
         self,
     ) -> Result<
@@ -140,17 +332,89 @@ where
         OneOf<<<E::Variants as Narrow<Target, Index>>::Remainder as TupleForm>::Tuple>,
     >
     where
-        Target: 'static,
         E::Variants: Narrow<Target, Index>,
     {
-        if self.value.is::<Target>() {
-            Ok(*self.value.downcast::<Target>().unwrap())
-        } else {
-            Err(OneOf {
-                value: self.value,
-                _pd: PhantomData,
-            })
+        match Narrow::<Target, Index>::narrow(self.value) {
+            Ok(target) => Ok(*target),
+            Err(value) => Err(OneOf { value }),
+        }
+    }
+
+    /// The same as `narrow`, but the `Target` is returned in the
+    /// `Box` that it is stored in, which allows unsized variants
+    /// such as `str` or `dyn Error` to be narrowed.
+    #[allow(clippy::type_complexity)]
+    pub fn narrow_boxed<Target, Index>(
+        self,
+    ) -> Result<
+        Box<Target>,
+        OneOf<<<E::Variants as Narrow<Target, Index>>::Remainder as TupleForm>::Tuple>,
+    >
+    where
+        Target: ?Sized,
+        E::Variants: Narrow<Target, Index>,
+    {
+        Narrow::<Target, Index>::narrow(self.value).map_err(|value| OneOf { value })
+    }
+
+    /// Borrow the active variant as a `Target`, if that
+    /// is the variant that is present.
+    pub fn narrow_ref<Target, Index>(&self) -> Option<&Target>
+    where
+        Target: ?Sized,
+        E::Variants: Narrow<Target, Index>,
+    {
+        self.value.narrow_ref()
+    }
+
+    /// Attempt to take a `Target` out of the `OneOf`, where the
+    /// `Target` may also be a variant of a `OneOf` that is itself
+    /// a variant, at any depth of nesting. The `OneOf` is returned
+    /// unchanged if the `Target` is not present. The `Target` must be
+    /// reachable through exactly one path of nested sets so that the
+    /// `Index` can be inferred.
+    pub fn narrow_deep<Target, Index>(self) -> Result<Target, OneOf<E>>
+    where
+        E::Variants: NarrowDeep<Target, Index>,
+    {
+        match self.value.narrow_deep() {
+            Ok(target) => Ok(*target),
+            Err(value) => Err(OneOf { value }),
+        }
+    }
+
+    /// Search the active variant and then its chain of `Error::source`s
+    /// for an error of type `T`, which finds errors no matter how many
+    /// layers of other errors wrap them.
+    ///
+    /// The error wrapped by an `io::Error` and the active variant of
+    /// a nested `OneOf` are also searched, even though neither is
+    /// returned as the `source` of the error that holds it.
+    pub fn find_source<T>(&self) -> Option<&T>
+    where
+        T: Error + 'static,
+        E::Variants: ErrorFold,
+    {
+        let mut error = Some(self.as_dyn_error());
+
+        while let Some(current) = error {
+            if let Some(t) = current.downcast_ref::<T>() {
+                return Some(t);
+            }
+            // `io::Error::source` skips over the error that it wraps
+            // and returns that error's source, so step into it instead.
+            // `OneOf` does the same with its active variant, and steps
+            // into it when asked through `StepInto`.
+            error = match current
+                .downcast_ref::<io::Error>()
+                .and_then(io::Error::get_ref)
+            {
+                Some(inner) => Some(inner),
+                None => StepInto::source(current),
+            };
         }
+
+        None
     }
 
     /// Turns the `OneOf` into a `OneOf` with a set of variants
@@ -158,18 +422,18 @@ where
     /// the same set of variants, but in a different order.
     pub fn broaden<Other, Index>(self) -> OneOf<Other>
     where
-        Other: TypeSet,
+        Other: TypeSet + ?Sized,
         Other::Variants: SupersetOf<E::Variants, Index>,
     {
         OneOf {
-            value: self.value,
-            _pd: PhantomData,
+            value: SupersetOf::embed(self.value),
         }
     }
 
     /// Attempt to split a subset of variants out of the `OneOf`,
     /// returning the remainder of possible variants if the value
     /// does not have one of the `TargetList` types.
+    #[allow(clippy::type_complexity)]
     pub fn subset<TargetList, Index>(
         self,
     ) -> Result<
@@ -177,30 +441,260 @@ where
         OneOf<<<E::Variants as SupersetOf<TargetList::Variants, Index>>::Remainder as TupleForm>::Tuple>,
     >
     where
-        TargetList: TypeSet,
-        E::Variants: IsFold + SupersetOf<TargetList::Variants, Index>,
-    {
-        if E::Variants::is_fold(&self.value) {
-            Ok(OneOf {
-                value: self.value,
-                _pd: PhantomData,
-            })
-        } else {
-            Err(OneOf {
-                value: self.value,
-                _pd: PhantomData,
-            })
+        TargetList: TypeSet + ?Sized,
+        E::Variants: SupersetOf<TargetList::Variants, Index>,
+    {
+        match SupersetOf::<TargetList::Variants, Index>::subset(self.value) {
+            Ok(value) => Ok(OneOf { value }),
+            Err(value) => Err(OneOf { value }),
+        }
+    }
+
+    /// Split a subset of variants out of the `OneOf` into an
+    /// enum that holds either one of the `TargetList` types or,
+    /// as its final variant, a `OneOf` of the remaining types.
+    /// This allows the local concerns to be handled in a single
+    /// `match` while the remainder is propagated to the caller.
+    /// Up to 8 types may be split out at once.
+    #[allow(clippy::type_complexity)]
+    pub fn split<TargetList, Index>(
+        self,
+    ) -> <TargetList as Split<
+        <<E::Variants as SupersetOf<<TargetList as TypeSet>::Variants, Index>>::Remainder as TupleForm>::Tuple,
+    >>::Output
+    where
+        TargetList: TypeSet
+            + Split<
+                <<E::Variants as SupersetOf<<TargetList as TypeSet>::Variants, Index>>::Remainder as TupleForm>::Tuple,
+            >,
+        E::Variants: SupersetOf<<TargetList as TypeSet>::Variants, Index>,
+    {
+        TargetList::split(self.subset())
+    }
+
+    /// Convert the `Target` variant into a `Mapped` type using the
+    /// provided function, if that is the variant that is present.
+    /// The returned `OneOf` has the `Target` type replaced by the
+    /// `Mapped` type, in the same position in the set of variants.
+    ///
+    /// If the `Mapped` type is already a part of the set of variants,
+    /// use `merge_variant` instead, which avoids a duplicate entry.
+    #[allow(clippy::type_complexity)]
+    pub fn map_variant<Target, Mapped, Index, F>(
+        self,
+        f: F,
+    ) -> OneOf<<<E::Variants as Replace<Target, Mapped, Index>>::Output as TupleForm>::Tuple>
+    where
+        E::Variants: Replace<Target, Mapped, Index>,
+        F: FnOnce(Target) -> Mapped,
+    {
+        OneOf {
+            value: self.value.replace(f),
         }
     }
 
+    /// Convert the `Target` variant into a `Mapped` type which is
+    /// already a part of the set of variants, using the provided
+    /// function, if that is the variant that is present. The returned
+    /// `OneOf` no longer contains the `Target` type.
+    #[allow(clippy::type_complexity)]
+    pub fn merge_variant<Target, Mapped, Index, MappedIndex, F>(
+        self,
+        f: F,
+    ) -> OneOf<<<E::Variants as Narrow<Target, Index>>::Remainder as TupleForm>::Tuple>
+    where
+        E::Variants: Narrow<Target, Index>,
+        <E::Variants as Narrow<Target, Index>>::Remainder: Contains<Mapped, MappedIndex>,
+        F: FnOnce(Target) -> Mapped,
+    {
+        let value = match Narrow::<Target, Index>::narrow(self.value) {
+            Ok(target) => Contains::<Mapped, MappedIndex>::inject(Box::new(f(*target))),
+            Err(remainder) => remainder,
+        };
+
+        OneOf { value }
+    }
+
+    /// Convert the `Target` variant into a `Mapped` type using the
+    /// provided fallible function, if that is the variant that is
+    /// present. The function may fail with its own set of `Errors`,
+    /// which are merged along with the `Mapped` type and the other
+    /// existing variants into the `Other` set, which must be a
+    /// superset of all of them.
+    pub fn try_map_variant<Target, Mapped, Errors, Other, Index, MappedIndex, ErrorsIndex, F>(
+        self,
+        f: F,
+    ) -> OneOf<Other>
+    where
+        Errors: TypeSet + ?Sized,
+        Other: TypeSet + ?Sized,
+        E::Variants: Narrow<Target, Index>,
+        Other::Variants: SupersetOf<Cons<Mapped, <E::Variants as Narrow<Target, Index>>::Remainder>, MappedIndex>
+            + SupersetOf<Errors::Variants, ErrorsIndex>,
+        F: FnOnce(Target) -> Result<Mapped, OneOf<Errors>>,
+    {
+        let mapped = match Narrow::<Target, Index>::narrow(self.value) {
+            Ok(target) => f(*target).map(|mapped| Cons::Head(Box::new(mapped))),
+            Err(remainder) => Ok(Cons::Tail(remainder)),
+        };
+
+        OneOf::from_try_mapped::<Mapped, _, Errors, MappedIndex, ErrorsIndex>(mapped)
+    }
+
+    /// The same as `try_map_variant`, but for conversions which
+    /// need to `.await` something while producing the `Mapped` type.
+    pub async fn try_map_variant_async<
+        Target,
+        Mapped,
+        Errors,
+        Other,
+        Index,
+        MappedIndex,
+        ErrorsIndex,
+        F,
+        Fut,
+    >(
+        self,
+        f: F,
+    ) -> OneOf<Other>
+    where
+        Errors: TypeSet + ?Sized,
+        Other: TypeSet + ?Sized,
+        E::Variants: Narrow<Target, Index>,
+        Other::Variants: SupersetOf<Cons<Mapped, <E::Variants as Narrow<Target, Index>>::Remainder>, MappedIndex>
+            + SupersetOf<Errors::Variants, ErrorsIndex>,
+        F: FnOnce(Target) -> Fut,
+        Fut: Future<Output = Result<Mapped, OneOf<Errors>>>,
+    {
+        let mapped = match Narrow::<Target, Index>::narrow(self.value) {
+            Ok(target) => f(*target).await.map(|mapped| Cons::Head(Box::new(mapped))),
+            Err(remainder) => Ok(Cons::Tail(remainder)),
+        };
+
+        OneOf::from_try_mapped::<Mapped, _, Errors, MappedIndex, ErrorsIndex>(mapped)
+    }
+
+    /// Shared by `try_map_variant` and its async counterpart to embed
+    /// either the mapped variants or the mapping errors into `Other`.
+    fn from_try_mapped<Mapped, Rem, Errors, MappedIndex, ErrorsIndex>(
+        mapped: Result<Cons<Mapped, Rem>, OneOf<Errors>>,
+    ) -> OneOf<E>
+    where
+        Errors: TypeSet + ?Sized,
+        E::Variants:
+            SupersetOf<Cons<Mapped, Rem>, MappedIndex> + SupersetOf<Errors::Variants, ErrorsIndex>,
+    {
+        let value = match mapped {
+            Ok(mapped) => SupersetOf::<Cons<Mapped, Rem>, MappedIndex>::embed(mapped),
+            Err(errors) => SupersetOf::<Errors::Variants, ErrorsIndex>::embed(errors.value),
+        };
+
+        OneOf { value }
+    }
+
+    /// Apply a `Visitor` to a reference to the active variant.
+    /// The visitor must be implemented for every type in the set,
+    /// which is most easily achieved with a generic implementation
+    /// for every type which meets some bound.
+    pub fn visit<V, Output>(&self, visitor: V) -> Output
+    where
+        E::Variants: VisitFold<V, Output>,
+    {
+        self.value.visit_fold(visitor)
+    }
+
+    /// Apply a `VisitorMut` to a mutable reference to the active variant.
+    pub fn visit_mut<V, Output>(&mut self, visitor: V) -> Output
+    where
+        E::Variants: VisitMutFold<V, Output>,
+    {
+        self.value.visit_mut_fold(visitor)
+    }
+
+    /// Apply a `VisitorOwned` to the active variant, consuming it.
+    pub fn visit_owned<V, Output>(self, visitor: V) -> Output
+    where
+        E::Variants: VisitOwnedFold<V, Output>,
+    {
+        self.value.visit_owned_fold(visitor)
+    }
+
+    /// Borrow the active variant as a `Target`, which is
+    /// usually a trait object that every variant implements,
+    /// using the provided `Projection`.
+    pub fn project<P, Target>(&self) -> &Target
+    where
+        Target: ?Sized,
+        E::Variants: ProjectFold<P, Target>,
+    {
+        self.value.project_fold()
+    }
+
+    /// Borrow the active variant as a `dyn Error`, if every
+    /// variant implements `Error`. Unlike the `Error` implementation
+    /// of `OneOf` itself, the returned trait object can be downcast
+    /// to the concrete type of the active variant.
+    pub fn as_dyn_error(&self) -> &(dyn Error + 'static)
+    where
+        E::Variants: ErrorFold,
+    {
+        self.value.error_fold()
+    }
+
+    /// Convert the active variant into a boxed `dyn Error`, if
+    /// every variant implements `Error`, `Send` and `Sync`. The
+    /// returned trait object can be downcast to the concrete type
+    /// of the active variant, and the existing allocation is reused.
+    ///
+    /// This is preferable to the standard library's `From` conversion
+    /// into a `Box<dyn Error + Send + Sync>`, which is also used by `?`,
+    /// because that boxes the `OneOf` itself and therefore hides the
+    /// concrete type of the active variant from downcasting.
+    pub fn into_box_dyn_error(self) -> Box<dyn Error + Send + Sync>
+    where
+        E::Variants: IntoErrorFold,
+    {
+        self.value.into_error_fold()
+    }
+
+    /// Flatten any variants which are themselves a `OneOf` into
+    /// the `Other` set, which must contain every non-`OneOf` variant
+    /// as well as every variant of the nested `OneOf`s. Because `Other`
+    /// is a set, types which appear multiple times across the nested
+    /// sets are merged into a single variant.
+    pub fn flatten<Other, Index>(self) -> OneOf<Other>
+    where
+        Other: TypeSet + ?Sized,
+        E::Variants: FlattenFold<Other, Index>,
+    {
+        self.value.flatten_fold()
+    }
+
+    /// Returns the name of the type of the active variant,
+    /// as given by `core::any::type_name`.
+    pub fn type_name(&self) -> &'static str {
+        self.value.type_name_fold()
+    }
+
+    /// Returns an adapter which displays the `OneOf` prefixed by
+    /// the type of its active variant, such as `Timeout: timed out`.
+    pub fn display_with_type(&self) -> DisplayWithType<'_, E>
+    where
+        E::Variants: fmt::Display,
+    {
+        DisplayWithType { one_of: self }
+    }
+
     /// For a `OneOf` with a single variant, return
     /// the contained value.
     pub fn take<Target>(self) -> Target
     where
-        Target: 'static,
         E: TypeSet<Variants = Cons<Target, End>>,
     {
-        *self.value.downcast::<Target>().unwrap()
+        match self.value {
+            Cons::Head(target) => *target,
+            Cons::Tail(end) => match end {},
+        }
     }
 
     /// Convert the `OneOf` to an owned enum for
@@ -218,6 +712,113 @@ where
     where
         E::EnumRef<'a>: From<&'a Self>,
     {
-        E::EnumRef::from(&self)
+        E::EnumRef::from(self)
+    }
+}
+
+impl OneOf<()> {
+    /// A `OneOf` with no possible variants can never be constructed,
+    /// which is what remains after every variant has been handled,
+    /// for example by repeatedly calling `narrow`. This allows such
+    /// a value to be used anywhere that a type is expected.
+    pub fn unreachable(self) -> ! {
+        match self.value {}
+    }
+}
+
+/// Displays a `OneOf` prefixed by the type of its
+/// active variant. Created by `OneOf::display_with_type`.
+pub struct DisplayWithType<'a, E: TypeSet + ?Sized> {
+    one_of: &'a OneOf<E>,
+}
+
+impl<E> fmt::Display for DisplayWithType<'_, E>
+where
+    E: TypeSet + ?Sized,
+    E::Variants: fmt::Display,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "{}: {}",
+            ShortTypeName(self.one_of.type_name()),
+            self.one_of.value
+        )
+    }
+}
+
+/// Extracts the success value from a `Result` whose error
+/// is a `OneOf<()>`, which has no possible variants. This
+/// is useful after every possible error has been handled.
+///
+/// This is named after the `unwrap-infallible` convention
+/// rather than `into_ok` to avoid colliding with the unstable
+/// `Result::into_ok` method in the standard library.
+pub trait UnwrapInfallible {
+    type Ok;
+
+    fn unwrap_infallible(self) -> Self::Ok;
+}
+
+impl<T> UnwrapInfallible for Result<T, OneOf<()>> {
+    type Ok = T;
+
+    fn unwrap_infallible(self) -> T {
+        match self {
+            Ok(t) => t,
+            Err(one_of) => one_of.unreachable(),
+        }
+    }
+}
+
+/// Conversion into a `OneOf<Other>`. Plain values are placed into
+/// the `Other` set directly, while values that are already a `OneOf`
+/// are broadened into it. The `Index` is inferred and determines which
+/// of the two happens.
+pub trait IntoOneOf<Other: TypeSet + ?Sized, Index> {
+    fn into_one_of(self) -> OneOf<Other>;
+}
+
+impl<T, Other, Index> IntoOneOf<Other, Placed<Index>> for T
+where
+    Other: TypeSet + ?Sized,
+    Other::Variants: Contains<T, Index>,
+{
+    fn into_one_of(self) -> OneOf<Other> {
+        OneOf::new(self)
+    }
+}
+
+impl<E, Other, Index> IntoOneOf<Other, Broadened<Index>> for OneOf<E>
+where
+    E: TypeSet + ?Sized,
+    Other: TypeSet + ?Sized,
+    Other::Variants: SupersetOf<E::Variants, Index>,
+{
+    fn into_one_of(self) -> OneOf<Other> {
+        self.broaden()
+    }
+}
+
+/// Flattens a nested `Result` into a single `Result` whose error
+/// is the union of the inner and outer errors, which may be plain
+/// values or a `OneOf`. The `Other` set must contain all of them.
+pub trait FlattenErr<T, Other: TypeSet + ?Sized, Index> {
+    fn flatten_err(self) -> Result<T, OneOf<Other>>;
+}
+
+impl<T, Inner, Outer, Other, InnerIndex, OuterIndex> FlattenErr<T, Other, (InnerIndex, OuterIndex)>
+    for Result<Result<T, Inner>, Outer>
+where
+    Inner: IntoOneOf<Other, InnerIndex>,
+    Outer: IntoOneOf<Other, OuterIndex>,
+    Other: TypeSet + ?Sized,
+{
+    fn flatten_err(self) -> Result<T, OneOf<Other>> {
+        match self {
+            Ok(Ok(t)) => Ok(t),
+            Ok(Err(inner)) => Err(inner.into_one_of()),
+            Err(outer) => Err(outer.into_one_of()),
+        }
     }
 }
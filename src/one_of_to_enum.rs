@@ -1,418 +1,524 @@
-use super::{OneOf, E1, E2, E3, E4, E5, E6, E7, E8, E9};
+use std::error::Error;
+
+use super::{OneOf, TypeSet, E0, E1, E2, E3, E4, E5, E6, E7, E8, E9};
+use crate::type_set::{Lift, Split};
+use crate::Cons;
 
 /* ------------------------- Enum conversions ----------------------- */
 
-impl<A> From<OneOf<(A,)>> for E1<A>
-where
-    A: 'static,
-{
+impl From<OneOf<()>> for E0 {
+    fn from(one_of: OneOf<()>) -> Self {
+        one_of.unreachable()
+    }
+}
+
+impl<'a> From<&'a OneOf<()>> for E0 {
+    fn from(one_of: &'a OneOf<()>) -> Self {
+        match one_of.value {}
+    }
+}
+
+impl<A> From<OneOf<(A,)>> for E1<A> {
     fn from(one_of: OneOf<(A,)>) -> Self {
-        E1::A(*one_of.value.downcast().unwrap())
+        match one_of.value {
+            Cons::Head(a) => E1::A(*a),
+            Cons::Tail(end) => match end {},
+        }
     }
 }
 
-impl<'a, A> From<&'a OneOf<(A,)>> for E1<&'a A>
-where
-    A: 'static,
-{
+// A blanket impl over `A::Lifted` would overlap with `From<A> for E1<A>`,
+// so each unsized `Lift` type gets its own impl.
+macro_rules! lifted_e1_from {
+    ($([$($generic:tt)*] $unsized:ty,)*) => {
+        $(
+            impl<$($generic)*> From<OneOf<($unsized,)>> for E1<Box<$unsized>> {
+                fn from(one_of: OneOf<($unsized,)>) -> Self {
+                    match one_of.value {
+                        Cons::Head(a) => E1::A(a),
+                        Cons::Tail(end) => match end {},
+                    }
+                }
+            }
+        )*
+    };
+}
+
+lifted_e1_from! {
+    [] str,
+    [T] [T],
+    ['a] dyn Error + 'a,
+    ['a] dyn Error + Send + 'a,
+    ['a] dyn Error + Send + Sync + 'a,
+}
+
+impl<'a, A: ?Sized + Lift> From<&'a OneOf<(A,)>> for E1<&'a A> {
     fn from(one_of: &'a OneOf<(A,)>) -> Self {
-        E1::A(one_of.value.downcast_ref().unwrap())
+        match &one_of.value {
+            Cons::Head(a) => E1::A(a),
+            Cons::Tail(end) => match *end {},
+        }
     }
 }
 
-impl<A, B> From<OneOf<(A, B)>> for E2<A, B>
-where
-    A: 'static,
-    B: 'static,
-{
+impl<A, B: ?Sized + Lift> From<OneOf<(A, B)>> for E2<A, B::Lifted> {
     fn from(one_of: OneOf<(A, B)>) -> Self {
-        if one_of.value.is::<A>() {
-            E2::A(*one_of.value.downcast().unwrap())
-        } else {
-            E2::B(*one_of.value.downcast().unwrap())
+        match one_of.value {
+            Cons::Head(a) => E2::A(*a),
+            Cons::Tail(Cons::Head(b)) => E2::B(B::lift(b)),
+            Cons::Tail(Cons::Tail(end)) => match end {},
         }
     }
 }
 
-impl<'a, A, B> From<&'a OneOf<(A, B)>> for E2<&'a A, &'a B>
-where
-    A: 'static,
-    B: 'static,
-{
+impl<'a, A, B: ?Sized + Lift> From<&'a OneOf<(A, B)>> for E2<&'a A, &'a B> {
     fn from(one_of: &'a OneOf<(A, B)>) -> Self {
-        if one_of.value.is::<A>() {
-            E2::A(one_of.value.downcast_ref().unwrap())
-        } else {
-            E2::B(one_of.value.downcast_ref().unwrap())
+        match &one_of.value {
+            Cons::Head(a) => E2::A(a),
+            Cons::Tail(Cons::Head(b)) => E2::B(b),
+            Cons::Tail(Cons::Tail(end)) => match *end {},
         }
     }
 }
 
-impl<A, B, C> From<OneOf<(A, B, C)>> for E3<A, B, C>
-where
-    A: 'static,
-    B: 'static,
-    C: 'static,
-{
+impl<A, B, C: ?Sized + Lift> From<OneOf<(A, B, C)>> for E3<A, B, C::Lifted> {
     fn from(one_of: OneOf<(A, B, C)>) -> Self {
-        if one_of.value.is::<A>() {
-            E3::A(*one_of.value.downcast().unwrap())
-        } else if one_of.value.is::<B>() {
-            E3::B(*one_of.value.downcast().unwrap())
-        } else {
-            E3::C(*one_of.value.downcast().unwrap())
+        match one_of.value {
+            Cons::Head(a) => E3::A(*a),
+            Cons::Tail(Cons::Head(b)) => E3::B(*b),
+            Cons::Tail(Cons::Tail(Cons::Head(c))) => E3::C(C::lift(c)),
+            Cons::Tail(Cons::Tail(Cons::Tail(end))) => match end {},
         }
     }
 }
 
-impl<'a, A, B, C> From<&'a OneOf<(A, B, C)>> for E3<&'a A, &'a B, &'a C>
-where
-    A: 'static,
-    B: 'static,
-    C: 'static,
-{
+impl<'a, A, B, C: ?Sized + Lift> From<&'a OneOf<(A, B, C)>> for E3<&'a A, &'a B, &'a C> {
     fn from(one_of: &'a OneOf<(A, B, C)>) -> Self {
-        if one_of.value.is::<A>() {
-            E3::A(one_of.value.downcast_ref().unwrap())
-        } else if one_of.value.is::<B>() {
-            E3::B(one_of.value.downcast_ref().unwrap())
-        } else {
-            E3::C(one_of.value.downcast_ref().unwrap())
+        match &one_of.value {
+            Cons::Head(a) => E3::A(a),
+            Cons::Tail(Cons::Head(b)) => E3::B(b),
+            Cons::Tail(Cons::Tail(Cons::Head(c))) => E3::C(c),
+            Cons::Tail(Cons::Tail(Cons::Tail(end))) => match *end {},
         }
     }
 }
 
-impl<A, B, C, D> From<OneOf<(A, B, C, D)>> for E4<A, B, C, D>
-where
-    A: 'static,
-    B: 'static,
-    C: 'static,
-    D: 'static,
-{
+impl<A, B, C, D: ?Sized + Lift> From<OneOf<(A, B, C, D)>> for E4<A, B, C, D::Lifted> {
     fn from(one_of: OneOf<(A, B, C, D)>) -> Self {
-        if one_of.value.is::<A>() {
-            E4::A(*one_of.value.downcast().unwrap())
-        } else if one_of.value.is::<B>() {
-            E4::B(*one_of.value.downcast().unwrap())
-        } else if one_of.value.is::<C>() {
-            E4::C(*one_of.value.downcast().unwrap())
-        } else {
-            E4::D(*one_of.value.downcast().unwrap())
+        match one_of.value {
+            Cons::Head(a) => E4::A(*a),
+            Cons::Tail(Cons::Head(b)) => E4::B(*b),
+            Cons::Tail(Cons::Tail(Cons::Head(c))) => E4::C(*c),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(d)))) => E4::D(D::lift(d)),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(end)))) => match end {},
         }
     }
 }
 
-impl<'a, A, B, C, D> From<&'a OneOf<(A, B, C, D)>> for E4<&'a A, &'a B, &'a C, &'a D>
-where
-    A: 'static,
-    B: 'static,
-    C: 'static,
-    D: 'static,
+impl<'a, A, B, C, D: ?Sized + Lift> From<&'a OneOf<(A, B, C, D)>>
+    for E4<&'a A, &'a B, &'a C, &'a D>
 {
     fn from(one_of: &'a OneOf<(A, B, C, D)>) -> Self {
-        if one_of.value.is::<A>() {
-            E4::A(one_of.value.downcast_ref().unwrap())
-        } else if one_of.value.is::<B>() {
-            E4::B(one_of.value.downcast_ref().unwrap())
-        } else if one_of.value.is::<C>() {
-            E4::C(one_of.value.downcast_ref().unwrap())
-        } else {
-            E4::D(one_of.value.downcast_ref().unwrap())
+        match &one_of.value {
+            Cons::Head(a) => E4::A(a),
+            Cons::Tail(Cons::Head(b)) => E4::B(b),
+            Cons::Tail(Cons::Tail(Cons::Head(c))) => E4::C(c),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(d)))) => E4::D(d),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(end)))) => match *end {},
         }
     }
 }
 
-impl<A, B, C, D, E> From<OneOf<(A, B, C, D, E)>> for E5<A, B, C, D, E>
-where
-    A: 'static,
-    B: 'static,
-    C: 'static,
-    D: 'static,
-    E: 'static,
-{
+impl<A, B, C, D, E: ?Sized + Lift> From<OneOf<(A, B, C, D, E)>> for E5<A, B, C, D, E::Lifted> {
     fn from(one_of: OneOf<(A, B, C, D, E)>) -> Self {
-        if one_of.value.is::<A>() {
-            E5::A(*one_of.value.downcast().unwrap())
-        } else if one_of.value.is::<B>() {
-            E5::B(*one_of.value.downcast().unwrap())
-        } else if one_of.value.is::<C>() {
-            E5::C(*one_of.value.downcast().unwrap())
-        } else if one_of.value.is::<D>() {
-            E5::D(*one_of.value.downcast().unwrap())
-        } else {
-            E5::E(*one_of.value.downcast().unwrap())
+        match one_of.value {
+            Cons::Head(a) => E5::A(*a),
+            Cons::Tail(Cons::Head(b)) => E5::B(*b),
+            Cons::Tail(Cons::Tail(Cons::Head(c))) => E5::C(*c),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(d)))) => E5::D(*d),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(e))))) => E5::E(E::lift(e)),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(end))))) => match end {},
         }
     }
 }
 
-impl<'a, A, B, C, D, E> From<&'a OneOf<(A, B, C, D, E)>> for E5<&'a A, &'a B, &'a C, &'a D, &'a E>
-where
-    A: 'static,
-    B: 'static,
-    C: 'static,
-    D: 'static,
-    E: 'static,
+impl<'a, A, B, C, D, E: ?Sized + Lift> From<&'a OneOf<(A, B, C, D, E)>>
+    for E5<&'a A, &'a B, &'a C, &'a D, &'a E>
 {
     fn from(one_of: &'a OneOf<(A, B, C, D, E)>) -> Self {
-        if one_of.value.is::<A>() {
-            E5::A(one_of.value.downcast_ref().unwrap())
-        } else if one_of.value.is::<B>() {
-            E5::B(one_of.value.downcast_ref().unwrap())
-        } else if one_of.value.is::<C>() {
-            E5::C(one_of.value.downcast_ref().unwrap())
-        } else if one_of.value.is::<D>() {
-            E5::D(one_of.value.downcast_ref().unwrap())
-        } else {
-            E5::E(one_of.value.downcast_ref().unwrap())
+        match &one_of.value {
+            Cons::Head(a) => E5::A(a),
+            Cons::Tail(Cons::Head(b)) => E5::B(b),
+            Cons::Tail(Cons::Tail(Cons::Head(c))) => E5::C(c),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(d)))) => E5::D(d),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(e))))) => E5::E(e),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(end))))) => match *end {},
         }
     }
 }
 
-impl<A, B, C, D, E, F> From<OneOf<(A, B, C, D, E, F)>> for E6<A, B, C, D, E, F>
-where
-    A: 'static,
-    B: 'static,
-    C: 'static,
-    D: 'static,
-    E: 'static,
-    F: 'static,
+impl<A, B, C, D, E, F: ?Sized + Lift> From<OneOf<(A, B, C, D, E, F)>>
+    for E6<A, B, C, D, E, F::Lifted>
 {
     fn from(one_of: OneOf<(A, B, C, D, E, F)>) -> Self {
-        if one_of.value.is::<A>() {
-            E6::A(*one_of.value.downcast().unwrap())
-        } else if one_of.value.is::<B>() {
-            E6::B(*one_of.value.downcast().unwrap())
-        } else if one_of.value.is::<C>() {
-            E6::C(*one_of.value.downcast().unwrap())
-        } else if one_of.value.is::<D>() {
-            E6::D(*one_of.value.downcast().unwrap())
-        } else if one_of.value.is::<E>() {
-            E6::E(*one_of.value.downcast().unwrap())
-        } else {
-            E6::F(*one_of.value.downcast().unwrap())
+        match one_of.value {
+            Cons::Head(a) => E6::A(*a),
+            Cons::Tail(Cons::Head(b)) => E6::B(*b),
+            Cons::Tail(Cons::Tail(Cons::Head(c))) => E6::C(*c),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(d)))) => E6::D(*d),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(e))))) => E6::E(*e),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(f)))))) => {
+                E6::F(F::lift(f))
+            }
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(end)))))) => {
+                match end {}
+            }
         }
     }
 }
 
-impl<'a, A, B, C, D, E, F> From<&'a OneOf<(A, B, C, D, E, F)>>
+impl<'a, A, B, C, D, E, F: ?Sized + Lift> From<&'a OneOf<(A, B, C, D, E, F)>>
     for E6<&'a A, &'a B, &'a C, &'a D, &'a E, &'a F>
-where
-    A: 'static,
-    B: 'static,
-    C: 'static,
-    D: 'static,
-    E: 'static,
-    F: 'static,
 {
     fn from(one_of: &'a OneOf<(A, B, C, D, E, F)>) -> Self {
-        if one_of.value.is::<A>() {
-            E6::A(one_of.value.downcast_ref().unwrap())
-        } else if one_of.value.is::<B>() {
-            E6::B(one_of.value.downcast_ref().unwrap())
-        } else if one_of.value.is::<C>() {
-            E6::C(one_of.value.downcast_ref().unwrap())
-        } else if one_of.value.is::<D>() {
-            E6::D(one_of.value.downcast_ref().unwrap())
-        } else if one_of.value.is::<E>() {
-            E6::E(one_of.value.downcast_ref().unwrap())
-        } else {
-            E6::F(one_of.value.downcast_ref().unwrap())
+        match &one_of.value {
+            Cons::Head(a) => E6::A(a),
+            Cons::Tail(Cons::Head(b)) => E6::B(b),
+            Cons::Tail(Cons::Tail(Cons::Head(c))) => E6::C(c),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(d)))) => E6::D(d),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(e))))) => E6::E(e),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(f)))))) => E6::F(f),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(end)))))) => {
+                match *end {}
+            }
         }
     }
 }
 
-impl<A, B, C, D, E, F, G> From<OneOf<(A, B, C, D, E, F, G)>> for E7<A, B, C, D, E, F, G>
-where
-    A: 'static,
-    B: 'static,
-    C: 'static,
-    D: 'static,
-    E: 'static,
-    F: 'static,
-    G: 'static,
+impl<A, B, C, D, E, F, G: ?Sized + Lift> From<OneOf<(A, B, C, D, E, F, G)>>
+    for E7<A, B, C, D, E, F, G::Lifted>
 {
     fn from(one_of: OneOf<(A, B, C, D, E, F, G)>) -> Self {
-        if one_of.value.is::<A>() {
-            E7::A(*one_of.value.downcast().unwrap())
-        } else if one_of.value.is::<B>() {
-            E7::B(*one_of.value.downcast().unwrap())
-        } else if one_of.value.is::<C>() {
-            E7::C(*one_of.value.downcast().unwrap())
-        } else if one_of.value.is::<D>() {
-            E7::D(*one_of.value.downcast().unwrap())
-        } else if one_of.value.is::<E>() {
-            E7::E(*one_of.value.downcast().unwrap())
-        } else if one_of.value.is::<F>() {
-            E7::F(*one_of.value.downcast().unwrap())
-        } else {
-            E7::G(*one_of.value.downcast().unwrap())
+        match one_of.value {
+            Cons::Head(a) => E7::A(*a),
+            Cons::Tail(Cons::Head(b)) => E7::B(*b),
+            Cons::Tail(Cons::Tail(Cons::Head(c))) => E7::C(*c),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(d)))) => E7::D(*d),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(e))))) => E7::E(*e),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(f)))))) => E7::F(*f),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(
+                g,
+            ))))))) => E7::G(G::lift(g)),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(
+                end,
+            ))))))) => match end {},
         }
     }
 }
 
-impl<'a, A, B, C, D, E, F, G> From<&'a OneOf<(A, B, C, D, E, F, G)>>
+impl<'a, A, B, C, D, E, F, G: ?Sized + Lift> From<&'a OneOf<(A, B, C, D, E, F, G)>>
     for E7<&'a A, &'a B, &'a C, &'a D, &'a E, &'a F, &'a G>
-where
-    A: 'static,
-    B: 'static,
-    C: 'static,
-    D: 'static,
-    E: 'static,
-    F: 'static,
-    G: 'static,
 {
     fn from(one_of: &'a OneOf<(A, B, C, D, E, F, G)>) -> Self {
-        if one_of.value.is::<A>() {
-            E7::A(one_of.value.downcast_ref().unwrap())
-        } else if one_of.value.is::<B>() {
-            E7::B(one_of.value.downcast_ref().unwrap())
-        } else if one_of.value.is::<C>() {
-            E7::C(one_of.value.downcast_ref().unwrap())
-        } else if one_of.value.is::<D>() {
-            E7::D(one_of.value.downcast_ref().unwrap())
-        } else if one_of.value.is::<E>() {
-            E7::E(one_of.value.downcast_ref().unwrap())
-        } else if one_of.value.is::<F>() {
-            E7::F(one_of.value.downcast_ref().unwrap())
-        } else {
-            E7::G(one_of.value.downcast_ref().unwrap())
+        match &one_of.value {
+            Cons::Head(a) => E7::A(a),
+            Cons::Tail(Cons::Head(b)) => E7::B(b),
+            Cons::Tail(Cons::Tail(Cons::Head(c))) => E7::C(c),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(d)))) => E7::D(d),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(e))))) => E7::E(e),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(f)))))) => E7::F(f),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(
+                g,
+            ))))))) => E7::G(g),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(
+                end,
+            ))))))) => match *end {},
         }
     }
 }
 
-impl<A, B, C, D, E, F, G, H> From<OneOf<(A, B, C, D, E, F, G, H)>> for E8<A, B, C, D, E, F, G, H>
-where
-    A: 'static,
-    B: 'static,
-    C: 'static,
-    D: 'static,
-    E: 'static,
-    F: 'static,
-    G: 'static,
-    H: 'static,
+impl<A, B, C, D, E, F, G, H: ?Sized + Lift> From<OneOf<(A, B, C, D, E, F, G, H)>>
+    for E8<A, B, C, D, E, F, G, H::Lifted>
 {
     fn from(one_of: OneOf<(A, B, C, D, E, F, G, H)>) -> Self {
-        if one_of.value.is::<A>() {
-            E8::A(*one_of.value.downcast().unwrap())
-        } else if one_of.value.is::<B>() {
-            E8::B(*one_of.value.downcast().unwrap())
-        } else if one_of.value.is::<C>() {
-            E8::C(*one_of.value.downcast().unwrap())
-        } else if one_of.value.is::<D>() {
-            E8::D(*one_of.value.downcast().unwrap())
-        } else if one_of.value.is::<E>() {
-            E8::E(*one_of.value.downcast().unwrap())
-        } else if one_of.value.is::<F>() {
-            E8::F(*one_of.value.downcast().unwrap())
-        } else if one_of.value.is::<G>() {
-            E8::G(*one_of.value.downcast().unwrap())
-        } else {
-            E8::H(*one_of.value.downcast().unwrap())
+        match one_of.value {
+            Cons::Head(a) => E8::A(*a),
+            Cons::Tail(Cons::Head(b)) => E8::B(*b),
+            Cons::Tail(Cons::Tail(Cons::Head(c))) => E8::C(*c),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(d)))) => E8::D(*d),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(e))))) => E8::E(*e),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(f)))))) => E8::F(*f),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(
+                g,
+            ))))))) => E8::G(*g),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(
+                Cons::Head(h),
+            ))))))) => E8::H(H::lift(h)),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(
+                Cons::Tail(end),
+            ))))))) => match end {},
         }
     }
 }
 
-impl<'a, A, B, C, D, E, F, G, H> From<&'a OneOf<(A, B, C, D, E, F, G, H)>>
+impl<'a, A, B, C, D, E, F, G, H: ?Sized + Lift> From<&'a OneOf<(A, B, C, D, E, F, G, H)>>
     for E8<&'a A, &'a B, &'a C, &'a D, &'a E, &'a F, &'a G, &'a H>
-where
-    A: 'static,
-    B: 'static,
-    C: 'static,
-    D: 'static,
-    E: 'static,
-    F: 'static,
-    G: 'static,
-    H: 'static,
 {
     fn from(one_of: &'a OneOf<(A, B, C, D, E, F, G, H)>) -> Self {
-        if one_of.value.is::<A>() {
-            E8::A(one_of.value.downcast_ref().unwrap())
-        } else if one_of.value.is::<B>() {
-            E8::B(one_of.value.downcast_ref().unwrap())
-        } else if one_of.value.is::<C>() {
-            E8::C(one_of.value.downcast_ref().unwrap())
-        } else if one_of.value.is::<D>() {
-            E8::D(one_of.value.downcast_ref().unwrap())
-        } else if one_of.value.is::<E>() {
-            E8::E(one_of.value.downcast_ref().unwrap())
-        } else if one_of.value.is::<F>() {
-            E8::F(one_of.value.downcast_ref().unwrap())
-        } else if one_of.value.is::<G>() {
-            E8::G(one_of.value.downcast_ref().unwrap())
-        } else {
-            E8::H(one_of.value.downcast_ref().unwrap())
+        match &one_of.value {
+            Cons::Head(a) => E8::A(a),
+            Cons::Tail(Cons::Head(b)) => E8::B(b),
+            Cons::Tail(Cons::Tail(Cons::Head(c))) => E8::C(c),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(d)))) => E8::D(d),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(e))))) => E8::E(e),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(f)))))) => E8::F(f),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(
+                g,
+            ))))))) => E8::G(g),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(
+                Cons::Head(h),
+            ))))))) => E8::H(h),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(
+                Cons::Tail(end),
+            ))))))) => match *end {},
         }
     }
 }
 
-impl<A, B, C, D, E, F, G, H, I> From<OneOf<(A, B, C, D, E, F, G, H, I)>>
-    for E9<A, B, C, D, E, F, G, H, I>
-where
-    A: 'static,
-    B: 'static,
-    C: 'static,
-    D: 'static,
-    E: 'static,
-    F: 'static,
-    G: 'static,
-    H: 'static,
-    I: 'static,
+impl<A, B, C, D, E, F, G, H, I: ?Sized + Lift> From<OneOf<(A, B, C, D, E, F, G, H, I)>>
+    for E9<A, B, C, D, E, F, G, H, I::Lifted>
 {
     fn from(one_of: OneOf<(A, B, C, D, E, F, G, H, I)>) -> Self {
-        if one_of.value.is::<A>() {
-            E9::A(*one_of.value.downcast().unwrap())
-        } else if one_of.value.is::<B>() {
-            E9::B(*one_of.value.downcast().unwrap())
-        } else if one_of.value.is::<C>() {
-            E9::C(*one_of.value.downcast().unwrap())
-        } else if one_of.value.is::<D>() {
-            E9::D(*one_of.value.downcast().unwrap())
-        } else if one_of.value.is::<E>() {
-            E9::E(*one_of.value.downcast().unwrap())
-        } else if one_of.value.is::<F>() {
-            E9::F(*one_of.value.downcast().unwrap())
-        } else if one_of.value.is::<G>() {
-            E9::G(*one_of.value.downcast().unwrap())
-        } else if one_of.value.is::<H>() {
-            E9::H(*one_of.value.downcast().unwrap())
-        } else {
-            E9::I(*one_of.value.downcast().unwrap())
+        match one_of.value {
+            Cons::Head(a) => E9::A(*a),
+            Cons::Tail(Cons::Head(b)) => E9::B(*b),
+            Cons::Tail(Cons::Tail(Cons::Head(c))) => E9::C(*c),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(d)))) => E9::D(*d),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(e))))) => E9::E(*e),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(f)))))) => E9::F(*f),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(
+                g,
+            ))))))) => E9::G(*g),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(
+                Cons::Head(h),
+            ))))))) => E9::H(*h),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(
+                Cons::Tail(Cons::Head(i)),
+            ))))))) => E9::I(I::lift(i)),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(
+                Cons::Tail(Cons::Tail(end)),
+            ))))))) => match end {},
         }
     }
 }
 
-impl<'a, A, B, C, D, E, F, G, H, I> From<&'a OneOf<(A, B, C, D, E, F, G, H, I)>>
+impl<'a, A, B, C, D, E, F, G, H, I: ?Sized + Lift> From<&'a OneOf<(A, B, C, D, E, F, G, H, I)>>
     for E9<&'a A, &'a B, &'a C, &'a D, &'a E, &'a F, &'a G, &'a H, &'a I>
-where
-    A: 'static,
-    B: 'static,
-    C: 'static,
-    D: 'static,
-    E: 'static,
-    F: 'static,
-    G: 'static,
-    H: 'static,
-    I: 'static,
 {
     fn from(one_of: &'a OneOf<(A, B, C, D, E, F, G, H, I)>) -> Self {
-        if one_of.value.is::<A>() {
-            E9::A(one_of.value.downcast_ref().unwrap())
-        } else if one_of.value.is::<B>() {
-            E9::B(one_of.value.downcast_ref().unwrap())
-        } else if one_of.value.is::<C>() {
-            E9::C(one_of.value.downcast_ref().unwrap())
-        } else if one_of.value.is::<D>() {
-            E9::D(one_of.value.downcast_ref().unwrap())
-        } else if one_of.value.is::<E>() {
-            E9::E(one_of.value.downcast_ref().unwrap())
-        } else if one_of.value.is::<F>() {
-            E9::F(one_of.value.downcast_ref().unwrap())
-        } else if one_of.value.is::<G>() {
-            E9::G(one_of.value.downcast_ref().unwrap())
-        } else if one_of.value.is::<H>() {
-            E9::H(one_of.value.downcast_ref().unwrap())
-        } else {
-            E9::I(one_of.value.downcast_ref().unwrap())
+        match &one_of.value {
+            Cons::Head(a) => E9::A(a),
+            Cons::Tail(Cons::Head(b)) => E9::B(b),
+            Cons::Tail(Cons::Tail(Cons::Head(c))) => E9::C(c),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(d)))) => E9::D(d),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(e))))) => E9::E(e),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(f)))))) => E9::F(f),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(
+                g,
+            ))))))) => E9::G(g),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(
+                Cons::Head(h),
+            ))))))) => E9::H(h),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(
+                Cons::Tail(Cons::Head(i)),
+            ))))))) => E9::I(i),
+            Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(
+                Cons::Tail(Cons::Tail(end)),
+            ))))))) => match *end {},
+        }
+    }
+}
+
+/* ------------------------- Split conversions ----------------------- */
+
+impl<A, Rest> Split<Rest> for (A,)
+where
+    Rest: TypeSet + ?Sized,
+{
+    type Output = E2<A, OneOf<Rest>>;
+
+    fn split(subset: Result<OneOf<Self>, OneOf<Rest>>) -> Self::Output {
+        match subset {
+            Ok(one_of) => match one_of.value {
+                Cons::Head(a) => E2::A(*a),
+                Cons::Tail(end) => match end {},
+            },
+            Err(rest) => E2::B(rest),
+        }
+    }
+}
+
+impl<A, B, Rest> Split<Rest> for (A, B)
+where
+    Rest: TypeSet + ?Sized,
+{
+    type Output = E3<A, B, OneOf<Rest>>;
+
+    fn split(subset: Result<OneOf<Self>, OneOf<Rest>>) -> Self::Output {
+        match subset {
+            Ok(one_of) => match one_of.value {
+                Cons::Head(a) => E3::A(*a),
+                Cons::Tail(Cons::Head(b)) => E3::B(*b),
+                Cons::Tail(Cons::Tail(end)) => match end {},
+            },
+            Err(rest) => E3::C(rest),
+        }
+    }
+}
+
+impl<A, B, C, Rest> Split<Rest> for (A, B, C)
+where
+    Rest: TypeSet + ?Sized,
+{
+    type Output = E4<A, B, C, OneOf<Rest>>;
+
+    fn split(subset: Result<OneOf<Self>, OneOf<Rest>>) -> Self::Output {
+        match subset {
+            Ok(one_of) => match one_of.value {
+                Cons::Head(a) => E4::A(*a),
+                Cons::Tail(Cons::Head(b)) => E4::B(*b),
+                Cons::Tail(Cons::Tail(Cons::Head(c))) => E4::C(*c),
+                Cons::Tail(Cons::Tail(Cons::Tail(end))) => match end {},
+            },
+            Err(rest) => E4::D(rest),
+        }
+    }
+}
+
+impl<A, B, C, D, Rest> Split<Rest> for (A, B, C, D)
+where
+    Rest: TypeSet + ?Sized,
+{
+    type Output = E5<A, B, C, D, OneOf<Rest>>;
+
+    fn split(subset: Result<OneOf<Self>, OneOf<Rest>>) -> Self::Output {
+        match subset {
+            Ok(one_of) => match one_of.value {
+                Cons::Head(a) => E5::A(*a),
+                Cons::Tail(Cons::Head(b)) => E5::B(*b),
+                Cons::Tail(Cons::Tail(Cons::Head(c))) => E5::C(*c),
+                Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(d)))) => E5::D(*d),
+                Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(end)))) => match end {},
+            },
+            Err(rest) => E5::E(rest),
+        }
+    }
+}
+
+impl<A, B, C, D, E, Rest> Split<Rest> for (A, B, C, D, E)
+where
+    Rest: TypeSet + ?Sized,
+{
+    type Output = E6<A, B, C, D, E, OneOf<Rest>>;
+
+    fn split(subset: Result<OneOf<Self>, OneOf<Rest>>) -> Self::Output {
+        match subset {
+            Ok(one_of) => match one_of.value {
+                Cons::Head(a) => E6::A(*a),
+                Cons::Tail(Cons::Head(b)) => E6::B(*b),
+                Cons::Tail(Cons::Tail(Cons::Head(c))) => E6::C(*c),
+                Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(d)))) => E6::D(*d),
+                Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(e))))) => E6::E(*e),
+                Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(end))))) => match end {},
+            },
+            Err(rest) => E6::F(rest),
+        }
+    }
+}
+
+impl<A, B, C, D, E, F, Rest> Split<Rest> for (A, B, C, D, E, F)
+where
+    Rest: TypeSet + ?Sized,
+{
+    type Output = E7<A, B, C, D, E, F, OneOf<Rest>>;
+
+    fn split(subset: Result<OneOf<Self>, OneOf<Rest>>) -> Self::Output {
+        match subset {
+            Ok(one_of) => match one_of.value {
+                Cons::Head(a) => E7::A(*a),
+                Cons::Tail(Cons::Head(b)) => E7::B(*b),
+                Cons::Tail(Cons::Tail(Cons::Head(c))) => E7::C(*c),
+                Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(d)))) => E7::D(*d),
+                Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(e))))) => E7::E(*e),
+                Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(f)))))) => {
+                    E7::F(*f)
+                }
+                Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(end)))))) => {
+                    match end {}
+                }
+            },
+            Err(rest) => E7::G(rest),
+        }
+    }
+}
+
+impl<A, B, C, D, E, F, G, Rest> Split<Rest> for (A, B, C, D, E, F, G)
+where
+    Rest: TypeSet + ?Sized,
+{
+    type Output = E8<A, B, C, D, E, F, G, OneOf<Rest>>;
+
+    fn split(subset: Result<OneOf<Self>, OneOf<Rest>>) -> Self::Output {
+        match subset {
+            Ok(one_of) => match one_of.value {
+                Cons::Head(a) => E8::A(*a),
+                Cons::Tail(Cons::Head(b)) => E8::B(*b),
+                Cons::Tail(Cons::Tail(Cons::Head(c))) => E8::C(*c),
+                Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(d)))) => E8::D(*d),
+                Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(e))))) => E8::E(*e),
+                Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(f)))))) => {
+                    E8::F(*f)
+                }
+                Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(
+                    Cons::Head(g),
+                )))))) => E8::G(*g),
+                Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(
+                    Cons::Tail(end),
+                )))))) => match end {},
+            },
+            Err(rest) => E8::H(rest),
+        }
+    }
+}
+
+impl<A, B, C, D, E, F, G, H, Rest> Split<Rest> for (A, B, C, D, E, F, G, H)
+where
+    Rest: TypeSet + ?Sized,
+{
+    type Output = E9<A, B, C, D, E, F, G, H, OneOf<Rest>>;
+
+    fn split(subset: Result<OneOf<Self>, OneOf<Rest>>) -> Self::Output {
+        match subset {
+            Ok(one_of) => match one_of.value {
+                Cons::Head(a) => E9::A(*a),
+                Cons::Tail(Cons::Head(b)) => E9::B(*b),
+                Cons::Tail(Cons::Tail(Cons::Head(c))) => E9::C(*c),
+                Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(d)))) => E9::D(*d),
+                Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(e))))) => E9::E(*e),
+                Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Head(f)))))) => {
+                    E9::F(*f)
+                }
+                Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(
+                    Cons::Head(g),
+                )))))) => E9::G(*g),
+                Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(
+                    Cons::Tail(Cons::Head(h)),
+                )))))) => E9::H(*h),
+                Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(Cons::Tail(
+                    Cons::Tail(Cons::Tail(end)),
+                )))))) => match end {},
+            },
+            Err(rest) => E9::I(rest),
         }
     }
 }
@@ -0,0 +1,159 @@
+use core::fmt;
+use std::error::Error;
+
+use crate::type_set::{Contains, FromErrorFold, IntoErrorFold, TypeSet};
+use crate::OneOf;
+
+/// A catch-all variant which holds any error that is not one of
+/// the precise types in a set. Library APIs can include it as the
+/// final variant, for example `OneOf<(NotFound, Timeout, Other)>`,
+/// so that new error types can be introduced later without changing
+/// the set, and therefore without breaking callers that `match` on
+/// the result of `to_enum`.
+///
+/// ```
+/// use std::io;
+/// use terrors::{OneOf, Other, E2};
+///
+/// #[derive(Debug)]
+/// struct Timeout;
+///
+/// impl std::fmt::Display for Timeout {
+///     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+///         write!(f, "timed out")
+///     }
+/// }
+///
+/// impl std::error::Error for Timeout {}
+///
+/// let o: OneOf<(Timeout, Other)> = OneOf::absorb(io::Error::other("disk on fire"));
+///
+/// match o.to_enum() {
+///     E2::A(Timeout) => unreachable!(),
+///     E2::B(other) => assert!(other.downcast_ref::<io::Error>().is_some()),
+/// }
+/// ```
+pub struct Other(pub Box<dyn Error + Send + Sync>);
+
+impl Other {
+    /// Wrap an error in `Other`.
+    pub fn new<T>(error: T) -> Other
+    where
+        T: Error + Send + Sync + 'static,
+    {
+        Other(Box::new(error))
+    }
+
+    /// Return the wrapped error.
+    pub fn into_inner(self) -> Box<dyn Error + Send + Sync> {
+        self.0
+    }
+
+    /// Returns `true` if the wrapped error is a `T`.
+    pub fn is<T>(&self) -> bool
+    where
+        T: Error + 'static,
+    {
+        self.0.is::<T>()
+    }
+
+    /// Borrow the wrapped error as a `T`, if it is one.
+    pub fn downcast_ref<T>(&self) -> Option<&T>
+    where
+        T: Error + 'static,
+    {
+        self.0.downcast_ref::<T>()
+    }
+
+    /// Take the wrapped error out as a `T`, or return the
+    /// `Other` unchanged if it is not one.
+    pub fn downcast<T>(self) -> Result<T, Other>
+    where
+        T: Error + 'static,
+    {
+        match self.0.downcast::<T>() {
+            Ok(t) => Ok(*t),
+            Err(error) => Err(Other(error)),
+        }
+    }
+}
+
+impl From<Box<dyn Error + Send + Sync>> for Other {
+    fn from(error: Box<dyn Error + Send + Sync>) -> Other {
+        Other(error)
+    }
+}
+
+/// `Other` is transparent when debug printed, and
+/// only shows the error that it actually holds.
+impl fmt::Debug for Other {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(formatter)
+    }
+}
+
+impl fmt::Display for Other {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(formatter)
+    }
+}
+
+impl Error for Other {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.0.source()
+    }
+
+    #[cfg(feature = "error_provide")]
+    fn provide<'a>(&'a self, request: &mut std::error::Request<'a>) {
+        self.0.provide(request)
+    }
+}
+
+impl<E> OneOf<E>
+where
+    E: TypeSet + ?Sized,
+{
+    /// Create a new `OneOf` from any error. If the error is one of
+    /// the precise types in the set then it is placed there, and
+    /// otherwise it is absorbed into the `Other` variant.
+    pub fn absorb<T, Index>(error: T) -> OneOf<E>
+    where
+        T: Error + Send + Sync + 'static,
+        E::Variants: FromErrorFold + Contains<Other, Index>,
+    {
+        OneOf::absorb_boxed(Box::new(error))
+    }
+
+    /// The same as `absorb`, but for an error that is already boxed.
+    /// If the error is itself an `Other`, the error that it wraps is
+    /// absorbed instead, so that it may still be placed into one of
+    /// the precise types and is never wrapped a second time.
+    pub fn absorb_boxed<Index>(error: Box<dyn Error + Send + Sync>) -> OneOf<E>
+    where
+        E::Variants: FromErrorFold + Contains<Other, Index>,
+    {
+        let error = match error.downcast::<Other>() {
+            Ok(other) => other.0,
+            Err(error) => error,
+        };
+
+        match OneOf::try_from_dyn_error(error) {
+            Ok(one_of) => one_of,
+            Err(error) => OneOf::new(Other(error)),
+        }
+    }
+
+    /// Turns the `OneOf` into a `OneOf` with an `Other` variant. Unlike
+    /// `broaden`, the current variants do not need to be a subset of the
+    /// `Target` variants: each value which is one of the precise `Target`
+    /// types is placed there, and every other value is absorbed into the
+    /// `Other` variant. This check happens at runtime.
+    pub fn broaden_open<Target, Index>(self) -> OneOf<Target>
+    where
+        Target: TypeSet + ?Sized,
+        E::Variants: IntoErrorFold,
+        Target::Variants: FromErrorFold + Contains<Other, Index>,
+    {
+        OneOf::absorb_boxed(self.into_box_dyn_error())
+    }
+}
@@ -0,0 +1,321 @@
+//! Retry an operation while it fails with one of a set of retryable
+//! error types. The retryable types are removed from the error, and
+//! the rest of its variants are merged with `RetriesExhausted` into
+//! the set that is returned.
+//!
+//! ```
+//! use std::time::Duration;
+//!
+//! use terrors::common::{RetriesExhausted, Timeout};
+//! use terrors::retry::{ManualClock, RetryPolicy};
+//! use terrors::OneOf;
+//!
+//! #[derive(Debug)]
+//! struct NotEnoughMemory;
+//!
+//! let policy = RetryPolicy::fixed(3, Duration::from_millis(10)).with_clock(ManualClock::new());
+//!
+//! let mut attempts = 0;
+//! let result: Result<(), OneOf<(NotEnoughMemory, RetriesExhausted)>> =
+//!     policy.retry_on::<(Timeout,)>().run(|| {
+//!         attempts += 1;
+//!         Err(OneOf::<(Timeout, NotEnoughMemory)>::new(Timeout::new()))
+//!     });
+//!
+//! let exhausted = result.unwrap_err().narrow::<RetriesExhausted, _>().unwrap();
+//! assert_eq!(exhausted.attempts(), Some(3));
+//! assert_eq!(policy.clock().elapsed(), Duration::from_millis(20));
+//! ```
+use core::any::type_name;
+use core::fmt;
+use core::marker::PhantomData;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::common::RetriesExhausted;
+use crate::type_set::{Contains, SupersetOf, TypeSet};
+use crate::OneOf;
+
+type Remainder<E, Retryable, Index> =
+    <<E as TypeSet>::Variants as SupersetOf<<Retryable as TypeSet>::Variants, Index>>::Remainder;
+
+/// Tells the time and waits between attempts. This allows tests
+/// to run a `RetryPolicy` or a `CircuitBreaker` deterministically.
+///
+/// [`CircuitBreaker`]: crate::circuit::CircuitBreaker
+pub trait Clock {
+    /// Returns the time elapsed since an arbitrary, fixed point.
+    fn now(&self) -> Duration;
+
+    /// Wait for the given duration.
+    fn sleep(&self, duration: Duration);
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+    fn now(&self) -> Duration {
+        (**self).now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        (**self).sleep(duration)
+    }
+}
+
+/// A `Clock` which uses `std::time::Instant`, and blocks
+/// the current thread with `std::thread::sleep`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        static START: OnceLock<Instant> = OnceLock::new();
+
+        START.get_or_init(Instant::now).elapsed()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration)
+    }
+}
+
+/// A `Clock` which only moves forward when it is told to. Sleeping
+/// returns immediately, but advances the clock and is recorded.
+#[derive(Debug, Default)]
+pub struct ManualClock {
+    now: Mutex<Duration>,
+    sleeps: Mutex<Vec<Duration>>,
+}
+
+impl ManualClock {
+    /// Create a new `ManualClock`.
+    pub fn new() -> ManualClock {
+        ManualClock::default()
+    }
+
+    /// Move the clock forward without recording a sleep.
+    pub fn advance(&self, duration: Duration) {
+        *self.now.lock().unwrap() += duration;
+    }
+
+    /// Returns each duration that was slept for, in order.
+    pub fn sleeps(&self) -> Vec<Duration> {
+        self.sleeps.lock().unwrap().clone()
+    }
+
+    /// Returns the total duration that was slept for.
+    pub fn elapsed(&self) -> Duration {
+        self.sleeps.lock().unwrap().iter().sum()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        *self.now.lock().unwrap()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        self.advance(duration);
+        self.sleeps.lock().unwrap().push(duration)
+    }
+}
+
+/// How long to wait before each retry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backoff {
+    /// Wait the same duration before every retry.
+    Fixed(Duration),
+    /// Wait for `initial` before the first retry, and then twice
+    /// as long as the previous wait, up to a limit of `max`.
+    Exponential {
+        /// The wait before the first retry.
+        initial: Duration,
+        /// The longest wait before any retry.
+        max: Duration,
+    },
+}
+
+impl Backoff {
+    /// Returns the wait before the given retry, counting from 0.
+    pub fn delay(&self, retry: u32) -> Duration {
+        match *self {
+            Backoff::Fixed(delay) => delay,
+            Backoff::Exponential { initial, max } => 1_u32
+                .checked_shl(retry)
+                .and_then(|factor| initial.checked_mul(factor))
+                .map_or(max, |delay| delay.min(max)),
+        }
+    }
+}
+
+/// Decides how many attempts `RetryOn::run` makes, and how long it waits
+/// between them. At least one attempt is always made.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy<C = SystemClock> {
+    max_attempts: u32,
+    backoff: Backoff,
+    jitter: bool,
+    clock: C,
+}
+
+impl RetryPolicy {
+    /// Make up to `max_attempts` attempts, waiting `delay` between them.
+    pub fn fixed(max_attempts: u32, delay: Duration) -> RetryPolicy {
+        RetryPolicy::new(max_attempts, Backoff::Fixed(delay))
+    }
+
+    /// Make up to `max_attempts` attempts, waiting `initial` after the
+    /// first one and doubling the wait after each one, up to `max`.
+    pub fn exponential(max_attempts: u32, initial: Duration, max: Duration) -> RetryPolicy {
+        RetryPolicy::new(max_attempts, Backoff::Exponential { initial, max })
+    }
+
+    /// Make up to `max_attempts` attempts, waiting according to `backoff`.
+    pub fn new(max_attempts: u32, backoff: Backoff) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            backoff,
+            jitter: false,
+            clock: SystemClock,
+        }
+    }
+}
+
+impl<C> RetryPolicy<C> {
+    /// Wait for a random duration between zero and the backoff delay
+    /// instead, so that many clients which failed at the same time do
+    /// not all retry at the same time.
+    pub fn jittered(self) -> RetryPolicy<C> {
+        RetryPolicy {
+            jitter: true,
+            ..self
+        }
+    }
+
+    /// Wait between attempts using a different `Clock`.
+    pub fn with_clock<C2: Clock>(self, clock: C2) -> RetryPolicy<C2> {
+        RetryPolicy {
+            max_attempts: self.max_attempts,
+            backoff: self.backoff,
+            jitter: self.jitter,
+            clock,
+        }
+    }
+
+    /// Returns the most attempts that will be made.
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts.max(1)
+    }
+
+    /// Returns the `Backoff` used between attempts.
+    pub fn backoff(&self) -> Backoff {
+        self.backoff
+    }
+
+    /// Borrow the `Clock` used between attempts.
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// Retry operations while they fail with one of the `Retryable`
+    /// types, which is the only set that needs to be named.
+    pub fn retry_on<Retryable>(&self) -> RetryOn<'_, Retryable, C>
+    where
+        Retryable: TypeSet + ?Sized,
+    {
+        RetryOn {
+            policy: self,
+            retryable: PhantomData,
+        }
+    }
+
+    /// Returns the wait before the given retry, counting from 0,
+    /// with jitter applied if it is enabled.
+    pub fn delay(&self, retry: u32) -> Duration {
+        let delay = self.backoff.delay(retry);
+
+        if !self.jitter {
+            return delay;
+        }
+
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u32(retry);
+        let nanos = u64::try_from(delay.as_nanos()).unwrap_or(u64::MAX);
+
+        Duration::from_nanos(hasher.finish() % nanos.saturating_add(1))
+    }
+}
+
+/// Retries an operation while it fails with one of the `Retryable`
+/// types, according to a `RetryPolicy`. Created by
+/// `RetryPolicy::retry_on`.
+pub struct RetryOn<'a, Retryable: ?Sized, C> {
+    policy: &'a RetryPolicy<C>,
+    retryable: PhantomData<fn(&Retryable)>,
+}
+
+impl<Retryable, C> RetryOn<'_, Retryable, C>
+where
+    Retryable: TypeSet + ?Sized,
+    C: Clock,
+{
+    /// Call `operation` until it succeeds, fails with an error that is
+    /// not one of the `Retryable` types, or has been attempted as many
+    /// times as the policy allows.
+    ///
+    /// The variants of the operation's error that are not `Retryable`
+    /// are merged along with `RetriesExhausted` into the `Other` set,
+    /// which must be a superset of all of them. This may be the same set
+    /// as the remaining variants, for example when they already include
+    /// the `RetriesExhausted` of an inner retry.
+    pub fn run<Other, E, T, F, Index, RestIndex, ExhaustedIndex>(
+        &self,
+        mut operation: F,
+    ) -> Result<T, OneOf<Other>>
+    where
+        Other: TypeSet + ?Sized,
+        E: TypeSet + ?Sized,
+        F: FnMut() -> Result<T, OneOf<E>>,
+        E::Variants: SupersetOf<Retryable::Variants, Index>,
+        Other::Variants: SupersetOf<Remainder<E, Retryable, Index>, RestIndex>
+            + Contains<RetriesExhausted, ExhaustedIndex>,
+    {
+        let policy = self.policy;
+        let mut attempt = 1;
+
+        loop {
+            let error = match operation() {
+                Ok(t) => return Ok(t),
+                Err(error) => error,
+            };
+
+            if let Err(rest) = SupersetOf::<Retryable::Variants, Index>::subset(error.value) {
+                return Err(OneOf {
+                    value: SupersetOf::<_, RestIndex>::embed(rest),
+                });
+            }
+
+            if attempt >= policy.max_attempts() {
+                let exhausted = RetriesExhausted::after_attempts(attempt);
+                return Err(OneOf {
+                    value: Contains::<_, ExhaustedIndex>::inject(Box::new(exhausted)),
+                });
+            }
+
+            policy.clock.sleep(policy.delay(attempt - 1));
+            attempt += 1;
+        }
+    }
+}
+
+impl<Retryable: ?Sized, C: fmt::Debug> fmt::Debug for RetryOn<'_, Retryable, C> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("RetryOn")
+            .field("retryable", &type_name::<Retryable>())
+            .field("policy", &self.policy)
+            .finish()
+    }
+}
@@ -0,0 +1,134 @@
+use core::fmt;
+use core::ops::Deref;
+use std::error::Error;
+use std::sync::Arc;
+
+use crate::type_set::TypeSet;
+use crate::OneOf;
+
+/// A `OneOf` behind an `Arc`, which is cheap to clone even when
+/// its variants are not `Clone`. This is useful when a single
+/// error must be reported to several waiters, for example by a
+/// cache which deduplicates concurrent requests or through a
+/// broadcast channel.
+///
+/// A `SharedOneOf` dereferences to the `OneOf` that it holds, so
+/// it can be inspected with `narrow_ref`, `as_enum`, `visit` etc...
+///
+/// ```
+/// use terrors::{OneOf, SharedOneOf};
+///
+/// struct Timeout;
+///
+/// let shared: SharedOneOf<(Timeout, u32)> = SharedOneOf::new(OneOf::new(Timeout));
+/// let waiter = shared.clone();
+///
+/// assert!(waiter.narrow_ref::<Timeout, _>().is_some());
+///
+/// drop(waiter);
+///
+/// let owned: OneOf<(Timeout, u32)> = shared.try_unwrap().ok().unwrap();
+/// assert!(owned.narrow::<Timeout, _>().is_ok());
+/// ```
+pub struct SharedOneOf<E: TypeSet + ?Sized> {
+    inner: Arc<OneOf<E>>,
+}
+
+impl<E> SharedOneOf<E>
+where
+    E: TypeSet + ?Sized,
+{
+    /// Create a new `SharedOneOf`.
+    pub fn new(one_of: OneOf<E>) -> SharedOneOf<E> {
+        SharedOneOf {
+            inner: Arc::new(one_of),
+        }
+    }
+
+    /// Take the `OneOf` back out if this is the only
+    /// reference to it, or return the `SharedOneOf`
+    /// unchanged if it is not.
+    pub fn try_unwrap(self) -> Result<OneOf<E>, SharedOneOf<E>> {
+        Arc::try_unwrap(self.inner).map_err(|inner| SharedOneOf { inner })
+    }
+
+    /// Take the `OneOf` back out if this is the only
+    /// reference to it, and otherwise clone it.
+    pub fn unwrap_or_clone(self) -> OneOf<E>
+    where
+        OneOf<E>: Clone,
+    {
+        Arc::unwrap_or_clone(self.inner)
+    }
+
+    /// Returns `true` if both `SharedOneOf`s refer to the same `OneOf`.
+    pub fn ptr_eq(&self, other: &SharedOneOf<E>) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl<E> Clone for SharedOneOf<E>
+where
+    E: TypeSet + ?Sized,
+{
+    fn clone(&self) -> Self {
+        SharedOneOf {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<E> Deref for SharedOneOf<E>
+where
+    E: TypeSet + ?Sized,
+{
+    type Target = OneOf<E>;
+
+    fn deref(&self) -> &OneOf<E> {
+        &self.inner
+    }
+}
+
+impl<E> From<OneOf<E>> for SharedOneOf<E>
+where
+    E: TypeSet + ?Sized,
+{
+    fn from(one_of: OneOf<E>) -> SharedOneOf<E> {
+        SharedOneOf::new(one_of)
+    }
+}
+
+impl<E> fmt::Debug for SharedOneOf<E>
+where
+    E: TypeSet + ?Sized,
+    OneOf<E>: fmt::Debug,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(formatter)
+    }
+}
+
+impl<E> fmt::Display for SharedOneOf<E>
+where
+    E: TypeSet + ?Sized,
+    OneOf<E>: fmt::Display,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(formatter)
+    }
+}
+
+impl<E> Error for SharedOneOf<E>
+where
+    E: TypeSet + ?Sized,
+    OneOf<E>: Error,
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.inner.source()
+    }
+
+    #[cfg(feature = "error_provide")]
+    fn provide<'a>(&'a self, request: &mut std::error::Request<'a>) {
+        self.inner.provide(request)
+    }
+}
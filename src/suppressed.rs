@@ -0,0 +1,224 @@
+use core::fmt;
+use core::ops::Deref;
+use std::error::Error;
+
+use crate::type_set::{Narrow, SupersetOf, TupleForm, TypeSet};
+use crate::{ErrorBag, IntoOneOf, OneOf};
+
+/// A `OneOf` error along with the secondary errors that were suppressed
+/// while it was being propagated, such as a failed rollback or unlock
+/// during cleanup. The suppressed errors have their own set `S`, and
+/// they are kept when the primary error is broadened or narrowed.
+///
+/// A `WithSuppressed` dereferences to the primary `OneOf`, so it can be
+/// inspected with `narrow_ref`, `as_enum`, `visit` etc... The suppressed
+/// errors are shown by the alternate form of `Debug`, `{:#?}`.
+///
+/// ```
+/// use terrors::{OneOf, WithSuppressed};
+///
+/// #[derive(Debug)]
+/// struct Timeout;
+/// #[derive(Debug)]
+/// struct RollbackFailed;
+///
+/// fn commit() -> Result<(), OneOf<(Timeout,)>> {
+///     Err(OneOf::new(Timeout))
+/// }
+///
+/// fn rollback() -> Result<(), RollbackFailed> {
+///     Err(RollbackFailed)
+/// }
+///
+/// fn transaction() -> Result<(), WithSuppressed<(Timeout,), (RollbackFailed,)>> {
+///     commit().map_err(|error| {
+///         let mut error = WithSuppressed::new(error);
+///         if let Err(cleanup) = rollback() {
+///             error.suppress(cleanup);
+///         }
+///         error
+///     })
+/// }
+///
+/// let error = transaction().unwrap_err();
+/// assert!(error.narrow_ref::<Timeout, _>().is_some());
+/// assert_eq!(error.suppressed().count(), 1);
+/// ```
+pub struct WithSuppressed<E: TypeSet + ?Sized, S: TypeSet + ?Sized> {
+    error: OneOf<E>,
+    suppressed: ErrorBag<S>,
+}
+
+impl<E, S> WithSuppressed<E, S>
+where
+    E: TypeSet + ?Sized,
+    S: TypeSet + ?Sized,
+{
+    /// Wrap a primary error, without any suppressed errors yet.
+    pub fn new(error: OneOf<E>) -> WithSuppressed<E, S> {
+        WithSuppressed {
+            error,
+            suppressed: ErrorBag::new(),
+        }
+    }
+
+    /// Attach a suppressed error, which may be any of the types in
+    /// the `S` set or a `OneOf` whose variants are a subset of it.
+    pub fn suppress<T, Index>(&mut self, error: T)
+    where
+        T: IntoOneOf<S, Index>,
+    {
+        self.suppressed.push(error)
+    }
+
+    /// The same as `suppress`, but by value, for use in chains.
+    pub fn with_suppressed<T, Index>(mut self, error: T) -> WithSuppressed<E, S>
+    where
+        T: IntoOneOf<S, Index>,
+    {
+        self.suppress(error);
+        self
+    }
+
+    /// Borrow the primary error.
+    pub fn error(&self) -> &OneOf<E> {
+        &self.error
+    }
+
+    /// Iterate over the suppressed errors, in the order that they were attached.
+    pub fn suppressed(&self) -> core::slice::Iter<'_, OneOf<S>> {
+        self.suppressed.iter()
+    }
+
+    /// Return the primary error and the suppressed errors.
+    pub fn into_parts(self) -> (OneOf<E>, ErrorBag<S>) {
+        (self.error, self.suppressed)
+    }
+
+    /// Turns the primary error into a `OneOf` with a set of variants
+    /// which is a superset of the current one, keeping the suppressed
+    /// errors.
+    pub fn broaden<Other, Index>(self) -> WithSuppressed<Other, S>
+    where
+        Other: TypeSet + ?Sized,
+        Other::Variants: SupersetOf<E::Variants, Index>,
+    {
+        WithSuppressed {
+            error: self.error.broaden(),
+            suppressed: self.suppressed,
+        }
+    }
+
+    /// Attempt to narrow the primary error to the `Target` type. The
+    /// suppressed errors are kept on both sides of the result.
+    #[allow(clippy::type_complexity)]
+    pub fn narrow<Target, Index>(
+        self,
+    ) -> Result<
+        WithSuppressed<(Target,), S>,
+        WithSuppressed<<<E::Variants as Narrow<Target, Index>>::Remainder as TupleForm>::Tuple, S>,
+    >
+    where
+        E::Variants: Narrow<Target, Index>,
+    {
+        match self.error.narrow::<Target, Index>() {
+            Ok(target) => Ok(WithSuppressed {
+                error: OneOf::new(target),
+                suppressed: self.suppressed,
+            }),
+            Err(error) => Err(WithSuppressed {
+                error,
+                suppressed: self.suppressed,
+            }),
+        }
+    }
+}
+
+impl<E, S> Deref for WithSuppressed<E, S>
+where
+    E: TypeSet + ?Sized,
+    S: TypeSet + ?Sized,
+{
+    type Target = OneOf<E>;
+
+    fn deref(&self) -> &OneOf<E> {
+        &self.error
+    }
+}
+
+impl<E, S> From<OneOf<E>> for WithSuppressed<E, S>
+where
+    E: TypeSet + ?Sized,
+    S: TypeSet + ?Sized,
+{
+    fn from(error: OneOf<E>) -> WithSuppressed<E, S> {
+        WithSuppressed::new(error)
+    }
+}
+
+impl<E, S> Clone for WithSuppressed<E, S>
+where
+    E: TypeSet + ?Sized,
+    S: TypeSet + ?Sized,
+    OneOf<E>: Clone,
+    OneOf<S>: Clone,
+{
+    fn clone(&self) -> WithSuppressed<E, S> {
+        WithSuppressed {
+            error: self.error.clone(),
+            suppressed: self.suppressed.clone(),
+        }
+    }
+}
+
+/// Only the primary error is shown when debug printed, but the
+/// alternate form `{:#?}` also shows the suppressed errors.
+impl<E, S> fmt::Debug for WithSuppressed<E, S>
+where
+    E: TypeSet + ?Sized,
+    S: TypeSet + ?Sized,
+    OneOf<E>: fmt::Debug,
+    OneOf<S>: fmt::Debug,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !formatter.alternate() {
+            return self.error.fmt(formatter);
+        }
+
+        formatter
+            .debug_struct("WithSuppressed")
+            .field("error", &self.error)
+            .field("suppressed", &self.suppressed)
+            .finish()
+    }
+}
+
+impl<E, S> fmt::Display for WithSuppressed<E, S>
+where
+    E: TypeSet + ?Sized,
+    S: TypeSet + ?Sized,
+    OneOf<E>: fmt::Display,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.error.fmt(formatter)
+    }
+}
+
+/// The suppressed errors are provided as an `ErrorBag<S>`.
+impl<E, S> Error for WithSuppressed<E, S>
+where
+    E: TypeSet + ?Sized,
+    S: TypeSet + ?Sized + 'static,
+    OneOf<E>: Error,
+    OneOf<S>: fmt::Debug,
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.error.source()
+    }
+
+    #[cfg(feature = "error_provide")]
+    fn provide<'a>(&'a self, request: &mut std::error::Request<'a>) {
+        request.provide_ref::<ErrorBag<S>>(&self.suppressed);
+        self.error.provide(request)
+    }
+}
@@ -1,57 +1,167 @@
 //! Type-level set inclusion and difference, inspired by frunk's approach: <https://archive.is/YwDMX>
-use core::any::Any;
+use core::any::{Any, TypeId};
 use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
 use std::error::Error;
+use std::io;
 
-use crate::{Cons, End, Recurse};
+use crate::{
+    Cons, End, IntoOneOf, Nested, OneOf, Projection, Recurse, Visitor, VisitorMut, VisitorOwned,
+};
 
 /* ------------------------- std::error::Error support ----------------------- */
 
-pub trait ErrorFold {
-    fn source_fold(any: &Box<dyn Any>) -> Option<&(dyn Error + 'static)>;
+impl<Head, Tail> Error for Cons<Head, Tail>
+where
+    Head: Error + ?Sized,
+    Tail: Error,
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Cons::Head(head) => head.source(),
+            Cons::Tail(tail) => tail.source(),
+        }
+    }
 
     #[cfg(feature = "error_provide")]
-    fn provide_fold<'a>(any: &'a Box<dyn Any>, request: &mut std::error::Request<'a>);
+    fn provide<'a>(&'a self, request: &mut std::error::Request<'a>) {
+        match self {
+            Cons::Head(head) => head.provide(request),
+            Cons::Tail(tail) => tail.provide(request),
+        }
+    }
+}
+
+/// Borrows a variant as a `dyn Error`, which also works for the
+/// unsized `dyn Error` variants that are used as catch-alls.
+pub trait AsDynError {
+    fn as_dyn_error(&self) -> &(dyn Error + 'static);
+}
+
+impl<T: Error + 'static> AsDynError for T {
+    fn as_dyn_error(&self) -> &(dyn Error + 'static) {
+        self
+    }
+}
+
+impl AsDynError for dyn Error + 'static {
+    fn as_dyn_error(&self) -> &(dyn Error + 'static) {
+        self
+    }
+}
+
+impl AsDynError for dyn Error + Send + 'static {
+    fn as_dyn_error(&self) -> &(dyn Error + 'static) {
+        self
+    }
+}
+
+impl AsDynError for dyn Error + Send + Sync + 'static {
+    fn as_dyn_error(&self) -> &(dyn Error + 'static) {
+        self
+    }
+}
+
+pub trait ErrorFold {
+    fn error_fold(&self) -> &(dyn Error + 'static);
 }
 
 impl ErrorFold for End {
-    fn source_fold(_: &Box<dyn Any>) -> Option<&(dyn Error + 'static)> {
-        unreachable!("source_fold called on End");
+    fn error_fold(&self) -> &(dyn Error + 'static) {
+        match *self {}
     }
+}
 
-    #[cfg(feature = "error_provide")]
-    fn provide_fold<'a>(_: &Box<dyn Any>, _: &mut std::error::Request<'a>) {
-        unreachable!("provide_fold called on End");
+impl<Head, Tail> ErrorFold for Cons<Head, Tail>
+where
+    Head: AsDynError + ?Sized,
+    Tail: ErrorFold,
+{
+    fn error_fold(&self) -> &(dyn Error + 'static) {
+        match self {
+            Cons::Head(head) => head.as_dyn_error(),
+            Cons::Tail(tail) => tail.error_fold(),
+        }
     }
 }
 
-impl<Head, Tail> Error for Cons<Head, Tail>
+pub trait IntoErrorFold {
+    fn into_error_fold(self) -> Box<dyn Error + Send + Sync>;
+}
+
+impl IntoErrorFold for End {
+    fn into_error_fold(self) -> Box<dyn Error + Send + Sync> {
+        match self {}
+    }
+}
+
+impl<Head, Tail> IntoErrorFold for Cons<Head, Tail>
 where
-    Head: Error,
-    Tail: Error,
+    Head: 'static + Error + Send + Sync,
+    Tail: IntoErrorFold,
 {
+    fn into_error_fold(self) -> Box<dyn Error + Send + Sync> {
+        match self {
+            Cons::Head(head) => head,
+            Cons::Tail(tail) => tail.into_error_fold(),
+        }
+    }
 }
 
-impl<Head, Tail> ErrorFold for Cons<Head, Tail>
+pub trait FromErrorFold: Sized {
+    fn from_error_fold(
+        error: Box<dyn Error + Send + Sync>,
+    ) -> Result<Self, Box<dyn Error + Send + Sync>>;
+}
+
+impl FromErrorFold for End {
+    fn from_error_fold(
+        error: Box<dyn Error + Send + Sync>,
+    ) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        Err(error)
+    }
+}
+
+impl<Head, Tail> FromErrorFold for Cons<Head, Tail>
 where
-    Cons<Head, Tail>: Error,
     Head: 'static + Error,
-    Tail: ErrorFold,
+    Tail: FromErrorFold,
 {
-    fn source_fold(any: &Box<dyn Any>) -> Option<&(dyn Error + 'static)> {
-        if let Some(head_ref) = any.downcast_ref::<Head>() {
-            head_ref.source()
-        } else {
-            Tail::source_fold(any)
+    fn from_error_fold(
+        error: Box<dyn Error + Send + Sync>,
+    ) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        match error.downcast::<Head>() {
+            Ok(head) => Ok(Cons::Head(head)),
+            Err(error) => Tail::from_error_fold(error).map(Cons::Tail),
         }
     }
+}
 
-    #[cfg(feature = "error_provide")]
-    fn provide_fold<'a>(any: &'a Box<dyn Any>, request: &mut std::error::Request<'a>) {
-        if let Some(head_ref) = any.downcast_ref::<Head>() {
-            head_ref.provide(request)
-        } else {
-            Tail::provide_fold(any, request)
+/* ------------------------- Projection support ----------------------- */
+
+pub trait ProjectFold<P, Target: ?Sized> {
+    fn project_fold(&self) -> &Target;
+}
+
+impl<P, Target: ?Sized> ProjectFold<P, Target> for End {
+    fn project_fold(&self) -> &Target {
+        match *self {}
+    }
+}
+
+impl<Head, Tail, P, Target> ProjectFold<P, Target> for Cons<Head, Tail>
+where
+    Head: ?Sized,
+    Tail: ProjectFold<P, Target>,
+    P: Projection<Head, Target = Target>,
+    Target: ?Sized,
+{
+    fn project_fold(&self) -> &Target {
+        match self {
+            Cons::Head(head) => P::project(head),
+            Cons::Tail(tail) => tail.project_fold(),
         }
     }
 }
@@ -60,144 +170,467 @@ where
 
 impl<Head, Tail> fmt::Display for Cons<Head, Tail>
 where
-    Head: fmt::Display,
+    Head: fmt::Display + ?Sized,
     Tail: fmt::Display,
 {
-    fn fmt(&self, _: &mut fmt::Formatter<'_>) -> fmt::Result {
-        unreachable!("Display called for Cons which is not constructable")
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Cons::Head(head) => head.fmt(formatter),
+            Cons::Tail(tail) => tail.fmt(formatter),
+        }
     }
 }
 
 impl fmt::Display for End {
     fn fmt(&self, _: &mut fmt::Formatter<'_>) -> fmt::Result {
-        unreachable!("Display::fmt called for an End, which is not constructible.")
+        match *self {}
     }
 }
 
-pub trait DisplayFold {
-    fn display_fold(any: &Box<dyn Any>, formatter: &mut fmt::Formatter<'_>) -> fmt::Result;
-}
-
-impl DisplayFold for End {
-    fn display_fold(_: &Box<dyn Any>, _: &mut fmt::Formatter<'_>) -> fmt::Result {
-        unreachable!("display_fold called on End");
-    }
-}
+/* ------------------------- Debug support ----------------------- */
 
-impl<Head, Tail> DisplayFold for Cons<Head, Tail>
+/// `Cons` is transparent when debug printed, and
+/// only shows the value that it actually holds.
+impl<Head, Tail> fmt::Debug for Cons<Head, Tail>
 where
-    Cons<Head, Tail>: fmt::Display,
-    Head: 'static + fmt::Display,
-    Tail: DisplayFold,
+    Head: fmt::Debug + ?Sized,
+    Tail: fmt::Debug,
 {
-    fn display_fold(any: &Box<dyn Any>, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if let Some(head_ref) = any.downcast_ref::<Head>() {
-            head_ref.fmt(formatter)
-        } else {
-            Tail::display_fold(any, formatter)
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Cons::Head(head) => head.fmt(formatter),
+            Cons::Tail(tail) => tail.fmt(formatter),
         }
     }
 }
 
-/* ------------------------- Debug support ----------------------- */
+/* ------------------------- Type name support ----------------------- */
 
-pub trait DebugFold {
-    fn debug_fold(any: &Box<dyn Any>, formatter: &mut fmt::Formatter<'_>) -> fmt::Result;
+pub trait TypeNameFold {
+    fn type_name_fold(&self) -> &'static str;
 }
 
-impl DebugFold for End {
-    fn debug_fold(_: &Box<dyn Any>, _: &mut fmt::Formatter<'_>) -> fmt::Result {
-        unreachable!("debug_fold called on End");
+impl TypeNameFold for End {
+    fn type_name_fold(&self) -> &'static str {
+        match *self {}
     }
 }
 
-impl<Head, Tail> DebugFold for Cons<Head, Tail>
+impl<Head, Tail> TypeNameFold for Cons<Head, Tail>
 where
-    Cons<Head, Tail>: fmt::Debug,
-    Head: 'static + fmt::Debug,
-    Tail: DebugFold,
+    Head: ?Sized,
+    Tail: TypeNameFold,
 {
-    fn debug_fold(any: &Box<dyn Any>, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if let Some(head_ref) = any.downcast_ref::<Head>() {
-            head_ref.fmt(formatter)
-        } else {
-            Tail::debug_fold(any, formatter)
+    fn type_name_fold(&self) -> &'static str {
+        match self {
+            Cons::Head(_) => core::any::type_name::<Head>(),
+            Cons::Tail(tail) => tail.type_name_fold(),
         }
     }
 }
 
-/* ------------------------- Clone support ----------------------- */
+/// Writes a name from `core::any::type_name` without the module
+/// paths, so `(u32, alloc::string::String)` becomes `(u32, String)`.
+pub struct ShortTypeName(pub &'static str);
+
+impl fmt::Display for ShortTypeName {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut rest = self.0;
 
-pub trait CloneFold {
-    fn clone_fold(any: &Box<dyn Any>) -> Box<dyn Any>;
+        while !rest.is_empty() {
+            let end = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == ':'))
+                .unwrap_or(rest.len());
+            let (path, after) = rest.split_at(end);
+
+            let name = path.rsplit("::").next().unwrap_or(path);
+            formatter.write_str(name)?;
+
+            let delimiter_len = after.chars().next().map_or(0, char::len_utf8);
+            formatter.write_str(&after[..delimiter_len])?;
+
+            rest = &after[delimiter_len..];
+        }
+
+        Ok(())
+    }
 }
 
+/* ------------------------- Clone support ----------------------- */
+
 impl Clone for End {
     fn clone(&self) -> End {
-        unreachable!("clone called for End");
+        match *self {}
     }
 }
 
 impl<Head, Tail> Clone for Cons<Head, Tail>
 where
-    Head: 'static + Clone,
-    Tail: CloneFold,
+    Head: Clone,
+    Tail: Clone,
 {
     fn clone(&self) -> Self {
-        unreachable!("clone called for Cons which is not constructable");
+        match self {
+            Cons::Head(head) => Cons::Head(head.clone()),
+            Cons::Tail(tail) => Cons::Tail(tail.clone()),
+        }
     }
 }
 
-impl CloneFold for End {
-    fn clone_fold(_: &Box<dyn Any>) -> Box<dyn Any> {
-        unreachable!("clone_fold called on End");
+fn _clone_test() {
+    fn is_clone<T: Clone>() {}
+
+    type T0 = <(String, u32) as TypeSet>::Variants;
+
+    is_clone::<T0>();
+}
+
+/* ------------------------- Any support ----------------------- */
+
+pub trait AnyFold: Sized {
+    fn from_any_fold(any: Box<dyn Any>) -> Result<Self, Box<dyn Any>>;
+
+    fn into_any_fold(self) -> Box<dyn Any>;
+
+    fn type_id_fold(&self) -> TypeId;
+
+    fn contains_type_id(type_id: TypeId) -> bool;
+}
+
+impl AnyFold for End {
+    fn from_any_fold(any: Box<dyn Any>) -> Result<Self, Box<dyn Any>> {
+        Err(any)
+    }
+
+    fn into_any_fold(self) -> Box<dyn Any> {
+        match self {}
+    }
+
+    fn type_id_fold(&self) -> TypeId {
+        match *self {}
+    }
+
+    fn contains_type_id(_: TypeId) -> bool {
+        false
     }
 }
 
-impl<Head, Tail> CloneFold for Cons<Head, Tail>
+impl<Head, Tail> AnyFold for Cons<Head, Tail>
 where
-    Head: 'static + Clone,
-    Tail: CloneFold,
+    Head: 'static,
+    Tail: AnyFold,
 {
-    fn clone_fold(any: &Box<dyn Any>) -> Box<dyn Any> {
-        if let Some(head_ref) = any.downcast_ref::<Head>() {
-            Box::new(head_ref.clone())
-        } else {
-            Tail::clone_fold(any)
+    fn from_any_fold(any: Box<dyn Any>) -> Result<Self, Box<dyn Any>> {
+        match any.downcast::<Head>() {
+            Ok(head) => Ok(Cons::Head(head)),
+            Err(any) => Tail::from_any_fold(any).map(Cons::Tail),
+        }
+    }
+
+    fn into_any_fold(self) -> Box<dyn Any> {
+        match self {
+            Cons::Head(head) => head,
+            Cons::Tail(tail) => tail.into_any_fold(),
         }
     }
+
+    fn type_id_fold(&self) -> TypeId {
+        match self {
+            Cons::Head(_) => TypeId::of::<Head>(),
+            Cons::Tail(tail) => tail.type_id_fold(),
+        }
+    }
+
+    fn contains_type_id(type_id: TypeId) -> bool {
+        type_id == TypeId::of::<Head>() || Tail::contains_type_id(type_id)
+    }
 }
 
-fn _clone_test() {
-    fn is_clone<T: Clone>() {}
+/* ------------------------- Visitor support ----------------------- */
 
-    type T0 = <(String, u32) as TypeSet>::Variants;
+pub trait VisitFold<V, Output> {
+    fn visit_fold(&self, visitor: V) -> Output;
+}
 
-    is_clone::<T0>();
+impl<V, Output> VisitFold<V, Output> for End {
+    fn visit_fold(&self, _: V) -> Output {
+        match *self {}
+    }
+}
+
+impl<Head, Tail, V, Output> VisitFold<V, Output> for Cons<Head, Tail>
+where
+    Head: ?Sized,
+    Tail: VisitFold<V, Output>,
+    V: Visitor<Head, Output = Output>,
+{
+    fn visit_fold(&self, visitor: V) -> Output {
+        match self {
+            Cons::Head(head) => visitor.visit(head),
+            Cons::Tail(tail) => tail.visit_fold(visitor),
+        }
+    }
 }
 
-/* ------------------------- Any::is support ----------------------- */
+pub trait VisitMutFold<V, Output> {
+    fn visit_mut_fold(&mut self, visitor: V) -> Output;
+}
 
-pub trait IsFold {
-    fn is_fold(any: &Box<dyn Any>) -> bool;
+impl<V, Output> VisitMutFold<V, Output> for End {
+    fn visit_mut_fold(&mut self, _: V) -> Output {
+        match *self {}
+    }
 }
 
-impl IsFold for End {
-    fn is_fold(_: &Box<dyn Any>) -> bool {
-        false
+impl<Head, Tail, V, Output> VisitMutFold<V, Output> for Cons<Head, Tail>
+where
+    Head: ?Sized,
+    Tail: VisitMutFold<V, Output>,
+    V: VisitorMut<Head, Output = Output>,
+{
+    fn visit_mut_fold(&mut self, visitor: V) -> Output {
+        match self {
+            Cons::Head(head) => visitor.visit_mut(head),
+            Cons::Tail(tail) => tail.visit_mut_fold(visitor),
+        }
     }
 }
 
-impl<Head, Tail> IsFold for Cons<Head, Tail>
+pub trait VisitOwnedFold<V, Output> {
+    fn visit_owned_fold(self, visitor: V) -> Output;
+}
+
+impl<V, Output> VisitOwnedFold<V, Output> for End {
+    fn visit_owned_fold(self, _: V) -> Output {
+        match self {}
+    }
+}
+
+impl<Head, Tail, V, Output> VisitOwnedFold<V, Output> for Cons<Head, Tail>
 where
-    Head: 'static,
-    Tail: IsFold,
+    Tail: VisitOwnedFold<V, Output>,
+    V: VisitorOwned<Head, Output = Output>,
 {
-    fn is_fold(any: &Box<dyn Any>) -> bool {
-        if any.is::<Head>() {
-            true
-        } else {
-            Tail::is_fold(any)
+    fn visit_owned_fold(self, visitor: V) -> Output {
+        match self {
+            Cons::Head(head) => visitor.visit_owned(*head),
+            Cons::Tail(tail) => tail.visit_owned_fold(visitor),
+        }
+    }
+}
+
+/* ------------------------- Iterator support ----------------------- */
+
+/// Base case: a single iterator determines the `Item` type.
+impl<Head> Iterator for Cons<Head, End>
+where
+    Head: Iterator,
+{
+    type Item = Head::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Cons::Head(head) => head.next(),
+            Cons::Tail(end) => match *end {},
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            Cons::Head(head) => head.size_hint(),
+            Cons::Tail(end) => match *end {},
+        }
+    }
+}
+
+/// Recursive case: every other iterator must have the same `Item` type.
+impl<Head, Next, Tail> Iterator for Cons<Head, Cons<Next, Tail>>
+where
+    Head: Iterator,
+    Cons<Next, Tail>: Iterator<Item = Head::Item>,
+{
+    type Item = Head::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Cons::Head(head) => head.next(),
+            Cons::Tail(tail) => tail.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            Cons::Head(head) => head.size_hint(),
+            Cons::Tail(tail) => tail.size_hint(),
+        }
+    }
+}
+
+/* ------------------------- Future support ----------------------- */
+
+/// Every element of a `Cons` is boxed, so it is never pinned in place.
+impl<Head: ?Sized, Tail> Unpin for Cons<Head, Tail> {}
+
+/// Base case: a single future determines the `Output` type.
+impl<Head> Future for Cons<Head, End>
+where
+    Head: Future + Unpin,
+{
+    type Output = Head::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.get_mut() {
+            Cons::Head(head) => Pin::new(&mut **head).poll(cx),
+            Cons::Tail(end) => match *end {},
+        }
+    }
+}
+
+/// Recursive case: every other future must have the same `Output` type.
+impl<Head, Next, Tail> Future for Cons<Head, Cons<Next, Tail>>
+where
+    Head: Future + Unpin,
+    Cons<Next, Tail>: Future<Output = Head::Output>,
+{
+    type Output = Head::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.get_mut() {
+            Cons::Head(head) => Pin::new(&mut **head).poll(cx),
+            Cons::Tail(tail) => Pin::new(tail).poll(cx),
+        }
+    }
+}
+
+/* ------------------------- io::Read support ----------------------- */
+
+impl io::Read for End {
+    fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+        match *self {}
+    }
+}
+
+impl<Head, Tail> io::Read for Cons<Head, Tail>
+where
+    Head: io::Read,
+    Tail: io::Read,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Cons::Head(head) => head.read(buf),
+            Cons::Tail(tail) => tail.read(buf),
+        }
+    }
+}
+
+/* ------------------------- io::BufRead support ----------------------- */
+
+impl io::BufRead for End {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        match *self {}
+    }
+
+    fn consume(&mut self, _: usize) {
+        match *self {}
+    }
+}
+
+impl<Head, Tail> io::BufRead for Cons<Head, Tail>
+where
+    Head: io::BufRead,
+    Tail: io::BufRead,
+{
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        match self {
+            Cons::Head(head) => head.fill_buf(),
+            Cons::Tail(tail) => tail.fill_buf(),
+        }
+    }
+
+    fn consume(&mut self, amt: usize) {
+        match self {
+            Cons::Head(head) => head.consume(amt),
+            Cons::Tail(tail) => tail.consume(amt),
+        }
+    }
+}
+
+/* ------------------------- io::Write support ----------------------- */
+
+impl io::Write for End {
+    fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+        match *self {}
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match *self {}
+    }
+}
+
+impl<Head, Tail> io::Write for Cons<Head, Tail>
+where
+    Head: io::Write,
+    Tail: io::Write,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Cons::Head(head) => head.write(buf),
+            Cons::Tail(tail) => tail.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Cons::Head(head) => head.flush(),
+            Cons::Tail(tail) => tail.flush(),
+        }
+    }
+}
+
+/* ------------------------- AsRef<str> support ----------------------- */
+
+impl AsRef<str> for End {
+    fn as_ref(&self) -> &str {
+        match *self {}
+    }
+}
+
+impl<Head, Tail> AsRef<str> for Cons<Head, Tail>
+where
+    Head: AsRef<str>,
+    Tail: AsRef<str>,
+{
+    fn as_ref(&self) -> &str {
+        match self {
+            Cons::Head(head) => (**head).as_ref(),
+            Cons::Tail(tail) => tail.as_ref(),
+        }
+    }
+}
+
+/* ------------------------- Flatten support ----------------------- */
+
+pub trait FlattenFold<Other: TypeSet + ?Sized, Index> {
+    fn flatten_fold(self) -> OneOf<Other>;
+}
+
+impl<Other: TypeSet + ?Sized> FlattenFold<Other, End> for End {
+    fn flatten_fold(self) -> OneOf<Other> {
+        match self {}
+    }
+}
+
+impl<Head, Tail, Other, HeadIndex, TailIndex> FlattenFold<Other, Cons<HeadIndex, TailIndex>>
+    for Cons<Head, Tail>
+where
+    Head: IntoOneOf<Other, HeadIndex>,
+    Tail: FlattenFold<Other, TailIndex>,
+    Other: TypeSet + ?Sized,
+{
+    fn flatten_fold(self) -> OneOf<Other> {
+        match self {
+            Cons::Head(head) => Head::into_one_of(*head),
+            Cons::Tail(tail) => tail.flatten_fold(),
         }
     }
 }
@@ -218,108 +651,110 @@ impl TypeSet for () {
     type EnumRef<'a> = E0 where Self: 'a;
 }
 
-impl<A> TypeSet for (A,) {
+impl<A: ?Sized + Lift> TypeSet for (A,) {
     type Variants = Cons<A, End>;
-    type Enum = E1<A>;
+    type Enum = E1<A::Lifted>;
     type EnumRef<'a> = E1<&'a A> where Self: 'a;
 }
 
-impl<A, B> TypeSet for (A, B) {
+impl<A, B: ?Sized + Lift> TypeSet for (A, B) {
     type Variants = Cons<A, Cons<B, End>>;
-    type Enum = E2<A, B>;
+    type Enum = E2<A, B::Lifted>;
     type EnumRef<'a> = E2<&'a A, &'a B> where Self: 'a;
 }
 
-impl<A, B, C> TypeSet for (A, B, C) {
+impl<A, B, C: ?Sized + Lift> TypeSet for (A, B, C) {
     type Variants = Cons<A, Cons<B, Cons<C, End>>>;
-    type Enum = E3<A, B, C>;
+    type Enum = E3<A, B, C::Lifted>;
     type EnumRef<'a> = E3<&'a A, &'a B, &'a C> where Self: 'a;
 }
 
-impl<A, B, C, D> TypeSet for (A, B, C, D) {
+impl<A, B, C, D: ?Sized + Lift> TypeSet for (A, B, C, D) {
     type Variants = Cons<A, Cons<B, Cons<C, Cons<D, End>>>>;
-    type Enum = E4<A, B, C, D>;
+    type Enum = E4<A, B, C, D::Lifted>;
     type EnumRef<'a> = E4<&'a A, &'a B, &'a C, &'a D> where Self: 'a;
 }
 
-impl<A, B, C, D, E> TypeSet for (A, B, C, D, E) {
+impl<A, B, C, D, E: ?Sized + Lift> TypeSet for (A, B, C, D, E) {
     type Variants = Cons<A, Cons<B, Cons<C, Cons<D, Cons<E, End>>>>>;
-    type Enum = E5<A, B, C, D, E>;
+    type Enum = E5<A, B, C, D, E::Lifted>;
     type EnumRef<'a> = E5<&'a A, &'a B, &'a C, &'a D, &'a E> where Self: 'a;
 }
 
-impl<A, B, C, D, E, F> TypeSet for (A, B, C, D, E, F) {
+impl<A, B, C, D, E, F: ?Sized + Lift> TypeSet for (A, B, C, D, E, F) {
     type Variants = Cons<A, Cons<B, Cons<C, Cons<D, Cons<E, Cons<F, End>>>>>>;
-    type Enum = E6<A, B, C, D, E, F>;
+    type Enum = E6<A, B, C, D, E, F::Lifted>;
     type EnumRef<'a> = E6<&'a A, &'a B, &'a C, &'a D, &'a E, &'a F> where Self: 'a;
 }
 
-impl<A, B, C, D, E, F, G> TypeSet for (A, B, C, D, E, F, G) {
+impl<A, B, C, D, E, F, G: ?Sized + Lift> TypeSet for (A, B, C, D, E, F, G) {
     type Variants = Cons<A, Cons<B, Cons<C, Cons<D, Cons<E, Cons<F, Cons<G, End>>>>>>>;
-    type Enum = E7<A, B, C, D, E, F, G>;
+    type Enum = E7<A, B, C, D, E, F, G::Lifted>;
     type EnumRef<'a> = E7<&'a A, &'a B, &'a C, &'a D, &'a E, &'a F, &'a G> where Self: 'a;
 }
 
-impl<A, B, C, D, E, F, G, H> TypeSet for (A, B, C, D, E, F, G, H) {
+impl<A, B, C, D, E, F, G, H: ?Sized + Lift> TypeSet for (A, B, C, D, E, F, G, H) {
     type Variants = Cons<A, Cons<B, Cons<C, Cons<D, Cons<E, Cons<F, Cons<G, Cons<H, End>>>>>>>>;
-    type Enum = E8<A, B, C, D, E, F, G, H>;
+    type Enum = E8<A, B, C, D, E, F, G, H::Lifted>;
     type EnumRef<'a> = E8<&'a A, &'a B, &'a C, &'a D, &'a E, &'a F, &'a G, &'a H> where Self: 'a;
 }
 
-impl<A, B, C, D, E, F, G, H, I> TypeSet for (A, B, C, D, E, F, G, H, I) {
+impl<A, B, C, D, E, F, G, H, I: ?Sized + Lift> TypeSet for (A, B, C, D, E, F, G, H, I) {
     type Variants =
         Cons<A, Cons<B, Cons<C, Cons<D, Cons<E, Cons<F, Cons<G, Cons<H, Cons<I, End>>>>>>>>>;
-    type Enum = E9<A, B, C, D, E, F, G, H, I>;
+    type Enum = E9<A, B, C, D, E, F, G, H, I::Lifted>;
     type EnumRef<'a> = E9<&'a A, &'a B, &'a C, &'a D, &'a E, &'a F, &'a G, &'a H, &'a I> where Self: 'a;
 }
 
 /* ------------------------- TupleForm implemented for TypeSet ----------------------- */
 
-pub trait TupleForm {
-    type Tuple: TypeSet;
+pub trait TupleForm: TypeNameFold {
+    type Tuple: TypeSet<Variants = Self> + ?Sized;
 }
 
 impl TupleForm for End {
     type Tuple = ();
 }
 
-impl<A> TupleForm for Cons<A, End> {
+impl<A: ?Sized + Lift> TupleForm for Cons<A, End> {
     type Tuple = (A,);
 }
 
-impl<A, B> TupleForm for Cons<A, Cons<B, End>> {
+impl<A, B: ?Sized + Lift> TupleForm for Cons<A, Cons<B, End>> {
     type Tuple = (A, B);
 }
 
-impl<A, B, C> TupleForm for Cons<A, Cons<B, Cons<C, End>>> {
+impl<A, B, C: ?Sized + Lift> TupleForm for Cons<A, Cons<B, Cons<C, End>>> {
     type Tuple = (A, B, C);
 }
 
-impl<A, B, C, D> TupleForm for Cons<A, Cons<B, Cons<C, Cons<D, End>>>> {
+impl<A, B, C, D: ?Sized + Lift> TupleForm for Cons<A, Cons<B, Cons<C, Cons<D, End>>>> {
     type Tuple = (A, B, C, D);
 }
 
-impl<A, B, C, D, E> TupleForm for Cons<A, Cons<B, Cons<C, Cons<D, Cons<E, End>>>>> {
+impl<A, B, C, D, E: ?Sized + Lift> TupleForm for Cons<A, Cons<B, Cons<C, Cons<D, Cons<E, End>>>>> {
     type Tuple = (A, B, C, D, E);
 }
 
-impl<A, B, C, D, E, F> TupleForm for Cons<A, Cons<B, Cons<C, Cons<D, Cons<E, Cons<F, End>>>>>> {
+impl<A, B, C, D, E, F: ?Sized + Lift> TupleForm
+    for Cons<A, Cons<B, Cons<C, Cons<D, Cons<E, Cons<F, End>>>>>>
+{
     type Tuple = (A, B, C, D, E, F);
 }
 
-impl<A, B, C, D, E, F, G> TupleForm
+impl<A, B, C, D, E, F, G: ?Sized + Lift> TupleForm
     for Cons<A, Cons<B, Cons<C, Cons<D, Cons<E, Cons<F, Cons<G, End>>>>>>>
 {
     type Tuple = (A, B, C, D, E, F, G);
 }
 
-impl<A, B, C, D, E, F, G, H> TupleForm
+impl<A, B, C, D, E, F, G, H: ?Sized + Lift> TupleForm
     for Cons<A, Cons<B, Cons<C, Cons<D, Cons<E, Cons<F, Cons<G, Cons<H, End>>>>>>>>
 {
     type Tuple = (A, B, C, D, E, F, G, H);
 }
 
-impl<A, B, C, D, E, F, G, H, I> TupleForm
+impl<A, B, C, D, E, F, G, H, I: ?Sized + Lift> TupleForm
     for Cons<A, Cons<B, Cons<C, Cons<D, Cons<E, Cons<F, Cons<G, Cons<H, Cons<I, End>>>>>>>>>
 {
     type Tuple = (A, B, C, D, E, F, G, H, I);
@@ -327,6 +762,64 @@ impl<A, B, C, D, E, F, G, H, I> TupleForm
 
 /* ------------------------- Lifted ----------------------- */
 
+/// Only the last element of a tuple may be unsized, so the last
+/// variant of a set may be a type such as `str`, `[T]` or `dyn Error`.
+/// Because enum variants must be sized, such a variant is held in
+/// its `Box` when the `OneOf` is converted to an enum.
+pub trait Lift {
+    type Lifted;
+
+    fn lift(boxed: Box<Self>) -> Self::Lifted;
+}
+
+impl<T> Lift for T {
+    type Lifted = T;
+
+    fn lift(boxed: Box<T>) -> T {
+        *boxed
+    }
+}
+
+impl Lift for str {
+    type Lifted = Box<str>;
+
+    fn lift(boxed: Box<str>) -> Box<str> {
+        boxed
+    }
+}
+
+impl<T> Lift for [T] {
+    type Lifted = Box<[T]>;
+
+    fn lift(boxed: Box<[T]>) -> Box<[T]> {
+        boxed
+    }
+}
+
+impl<'a> Lift for dyn Error + 'a {
+    type Lifted = Box<dyn Error + 'a>;
+
+    fn lift(boxed: Box<dyn Error + 'a>) -> Box<dyn Error + 'a> {
+        boxed
+    }
+}
+
+impl<'a> Lift for dyn Error + Send + 'a {
+    type Lifted = Box<dyn Error + Send + 'a>;
+
+    fn lift(boxed: Box<dyn Error + Send + 'a>) -> Box<dyn Error + Send + 'a> {
+        boxed
+    }
+}
+
+impl<'a> Lift for dyn Error + Send + Sync + 'a {
+    type Lifted = Box<dyn Error + Send + Sync + 'a>;
+
+    fn lift(boxed: Box<dyn Error + Send + Sync + 'a>) -> Box<dyn Error + Send + Sync + 'a> {
+        boxed
+    }
+}
+
 pub enum E0 {}
 pub enum E1<A> {
     A(A),
@@ -397,42 +890,98 @@ pub enum E9<A, B, C, D, E, F, G, H, I> {
     I(I),
 }
 
+/* ------------------------- Split ----------------------- */
+
+/// Maps a set of types that are being split out of a `OneOf` to the
+/// enum returned by `OneOf::split`, where the final variant holds a
+/// `OneOf` of the `Rest` of the types that were not split out.
+pub trait Split<Rest: TypeSet + ?Sized>: TypeSet + Sized {
+    type Output;
+
+    fn split(subset: Result<OneOf<Self>, OneOf<Rest>>) -> Self::Output;
+}
+
 /* ------------------------- Contains ----------------------- */
 
 /// A trait that assists with compile-time type set inclusion testing.
 /// The `Index` parameter is either `End` or `Cons<...>` depending on
 /// whether the trait implementation is a base case or the recursive
 /// case.
-pub trait Contains<T, Index> {}
+pub trait Contains<T: ?Sized, Index>: Sized {
+    fn inject(t: Box<T>) -> Self;
+}
 
 /// Base case implementation for when the Cons Head is T.
-impl<T, Tail> Contains<T, End> for Cons<T, Tail> {}
+impl<T: ?Sized, Tail> Contains<T, End> for Cons<T, Tail> {
+    fn inject(t: Box<T>) -> Self {
+        Cons::Head(t)
+    }
+}
 
 /// Recursive case for when the Cons Tail contains T.
-impl<T, Index, Head, Tail> Contains<T, Cons<Index, ()>> for Cons<Head, Tail> where
-    Tail: Contains<T, Index>
+impl<T: ?Sized, Index, Head: ?Sized, Tail> Contains<T, Cons<Index, ()>> for Cons<Head, Tail>
+where
+    Tail: Contains<T, Index>,
 {
+    fn inject(t: Box<T>) -> Self {
+        Cons::Tail(Tail::inject(t))
+    }
 }
 
 /* ------------------------- Narrow ----------------------- */
 
 /// A trait for pulling a specific type out of a Variants at compile-time
 /// and having access to the other types as the Remainder.
-pub trait Narrow<Target, Index>: TupleForm {
+pub trait Narrow<Target: ?Sized, Index>: TupleForm + Sized {
     type Remainder: TupleForm;
+
+    /// Take the Target out if it is the active variant,
+    /// or return the Remainder if it is not.
+    fn narrow(self) -> Result<Box<Target>, Self::Remainder>;
+
+    /// Borrow the Target if it is the active variant.
+    fn narrow_ref(&self) -> Option<&Target>;
+
+    /// Hold the Target as the active variant.
+    fn inject(target: Box<Target>) -> Self;
+
+    /// Hold the active variant of the Remainder.
+    fn widen(remainder: Self::Remainder) -> Self;
 }
 
 /// Base case where the search Target is in the Head of the Variants.
-impl<Target, Tail> Narrow<Target, End> for Cons<Target, Tail>
+impl<Target: ?Sized, Tail> Narrow<Target, End> for Cons<Target, Tail>
 where
     Tail: TupleForm,
     Cons<Target, Tail>: TupleForm,
 {
     type Remainder = Tail;
+
+    fn narrow(self) -> Result<Box<Target>, Tail> {
+        match self {
+            Cons::Head(target) => Ok(target),
+            Cons::Tail(tail) => Err(tail),
+        }
+    }
+
+    fn narrow_ref(&self) -> Option<&Target> {
+        match self {
+            Cons::Head(target) => Some(target),
+            Cons::Tail(_) => None,
+        }
+    }
+
+    fn inject(target: Box<Target>) -> Self {
+        Cons::Head(target)
+    }
+
+    fn widen(remainder: Tail) -> Self {
+        Cons::Tail(remainder)
+    }
 }
 
 /// Recursive case where the search Target is in the Tail of the Variants.
-impl<Head, Tail, Target, Index> Narrow<Target, Recurse<Index>> for Cons<Head, Tail>
+impl<Head: ?Sized, Tail, Target: ?Sized, Index> Narrow<Target, Recurse<Index>> for Cons<Head, Tail>
 where
     Tail: Narrow<Target, Index>,
     Tail: TupleForm,
@@ -440,6 +989,31 @@ where
     Cons<Head, <Tail as Narrow<Target, Index>>::Remainder>: TupleForm,
 {
     type Remainder = Cons<Head, <Tail as Narrow<Target, Index>>::Remainder>;
+
+    fn narrow(self) -> Result<Box<Target>, Self::Remainder> {
+        match self {
+            Cons::Head(head) => Err(Cons::Head(head)),
+            Cons::Tail(tail) => tail.narrow().map_err(Cons::Tail),
+        }
+    }
+
+    fn narrow_ref(&self) -> Option<&Target> {
+        match self {
+            Cons::Head(_) => None,
+            Cons::Tail(tail) => tail.narrow_ref(),
+        }
+    }
+
+    fn inject(target: Box<Target>) -> Self {
+        Cons::Tail(Tail::inject(target))
+    }
+
+    fn widen(remainder: Self::Remainder) -> Self {
+        match remainder {
+            Cons::Head(head) => Cons::Head(head),
+            Cons::Tail(tail) => Cons::Tail(Tail::widen(tail)),
+        }
+    }
 }
 
 fn _narrow_test() {
@@ -455,21 +1029,170 @@ fn _narrow_test() {
     can_narrow::<T0, String, Cons<u32, End>, _>();
 }
 
+/* ------------------------- NarrowDeep ----------------------- */
+
+/// A trait for pulling a specific type out of a Variants, or out of
+/// a `OneOf` that is nested in the Variants, at any depth. The `Index`
+/// is the path to the Target, where `Nested` descends into a `OneOf`.
+pub trait NarrowDeep<Target, Index>: Sized {
+    /// Take the Target out if it is the active variant at the end
+    /// of the path, or return the Variants unchanged if it is not.
+    fn narrow_deep(self) -> Result<Box<Target>, Self>;
+}
+
+/// Base case where the search Target is in the Head of the Variants.
+impl<Target, Tail> NarrowDeep<Target, End> for Cons<Target, Tail> {
+    fn narrow_deep(self) -> Result<Box<Target>, Self> {
+        match self {
+            Cons::Head(target) => Ok(target),
+            Cons::Tail(tail) => Err(Cons::Tail(tail)),
+        }
+    }
+}
+
+/// Nested case where the search Target is inside of the `OneOf` in the Head of the Variants.
+impl<Inner, Tail, Target, Index> NarrowDeep<Target, Nested<Index>> for Cons<OneOf<Inner>, Tail>
+where
+    Inner: TypeSet + ?Sized,
+    Inner::Variants: NarrowDeep<Target, Index>,
+{
+    fn narrow_deep(self) -> Result<Box<Target>, Self> {
+        match self {
+            Cons::Head(inner) => inner
+                .value
+                .narrow_deep()
+                .map_err(|value| Cons::Head(Box::new(OneOf { value }))),
+            Cons::Tail(tail) => Err(Cons::Tail(tail)),
+        }
+    }
+}
+
+/// Recursive case where the search Target is in the Tail of the Variants.
+impl<Head, Tail, Target, Index> NarrowDeep<Target, Recurse<Index>> for Cons<Head, Tail>
+where
+    Head: ?Sized,
+    Tail: NarrowDeep<Target, Index>,
+{
+    fn narrow_deep(self) -> Result<Box<Target>, Self> {
+        match self {
+            Cons::Head(head) => Err(Cons::Head(head)),
+            Cons::Tail(tail) => tail.narrow_deep().map_err(Cons::Tail),
+        }
+    }
+}
+
+fn _narrow_deep_test() {
+    fn can_narrow_deep<Types, Target, Index>()
+    where
+        Types: NarrowDeep<Target, Index>,
+    {
+    }
+
+    type T0 = <(u32, OneOf<(String, OneOf<(u8,)>)>) as TypeSet>::Variants;
+
+    can_narrow_deep::<T0, u32, _>();
+    can_narrow_deep::<T0, String, _>();
+    can_narrow_deep::<T0, u8, _>();
+}
+
+/* ------------------------- Replace ----------------------- */
+
+/// A trait for swapping a specific type in a Variants for another type
+/// at compile-time, keeping the position of the replaced type.
+pub trait Replace<Target, With, Index>: TupleForm {
+    type Output: TupleForm;
+
+    /// Convert the Target with the provided function
+    /// if it is the active variant.
+    fn replace<F>(self, f: F) -> Self::Output
+    where
+        F: FnOnce(Target) -> With;
+}
+
+/// Base case where the Target to replace is in the Head of the Variants.
+impl<Target, With, Tail> Replace<Target, With, End> for Cons<Target, Tail>
+where
+    Cons<Target, Tail>: TupleForm,
+    Cons<With, Tail>: TupleForm,
+{
+    type Output = Cons<With, Tail>;
+
+    fn replace<F>(self, f: F) -> Self::Output
+    where
+        F: FnOnce(Target) -> With,
+    {
+        match self {
+            Cons::Head(target) => Cons::Head(Box::new(f(*target))),
+            Cons::Tail(tail) => Cons::Tail(tail),
+        }
+    }
+}
+
+/// Recursive case where the Target to replace is in the Tail of the Variants.
+impl<Head: ?Sized, Tail, Target, With, Index> Replace<Target, With, Recurse<Index>>
+    for Cons<Head, Tail>
+where
+    Tail: Replace<Target, With, Index>,
+    Cons<Head, Tail>: TupleForm,
+    Cons<Head, <Tail as Replace<Target, With, Index>>::Output>: TupleForm,
+{
+    type Output = Cons<Head, <Tail as Replace<Target, With, Index>>::Output>;
+
+    fn replace<F>(self, f: F) -> Self::Output
+    where
+        F: FnOnce(Target) -> With,
+    {
+        match self {
+            Cons::Head(head) => Cons::Head(head),
+            Cons::Tail(tail) => Cons::Tail(tail.replace(f)),
+        }
+    }
+}
+
+fn _replace_test() {
+    fn can_replace<Types, Target, With, Output, Index>()
+    where
+        Types: Replace<Target, With, Index, Output = Output>,
+    {
+    }
+
+    type T0 = <(u32, String, u8) as TypeSet>::Variants;
+
+    can_replace::<T0, u32, i32, <(i32, String, u8) as TypeSet>::Variants, _>();
+    can_replace::<T0, String, u64, <(u32, u64, u8) as TypeSet>::Variants, _>();
+    can_replace::<T0, u8, u8, T0, _>();
+}
+
 /* ------------------------- SupersetOf ----------------------- */
 
 /// When all types in a Variants are present in a second Variants
-pub trait SupersetOf<Other, Index> {
+pub trait SupersetOf<Other, Index>: Sized {
     type Remainder: TupleForm;
+
+    /// Hold the active variant of the subset.
+    fn embed(subset: Other) -> Self;
+
+    /// Take the active variant out as the subset if it is one
+    /// of the subset's types, or return the Remainder if it is not.
+    fn subset(self) -> Result<Other, Self::Remainder>;
 }
 
 /// Base case
 impl<T: TupleForm> SupersetOf<End, End> for T {
     type Remainder = T;
+
+    fn embed(subset: End) -> T {
+        match subset {}
+    }
+
+    fn subset(self) -> Result<End, T> {
+        Err(self)
+    }
 }
 
 /// Recursive case - more complex because we have to reason about the Index itself as a
 /// heterogenous list.
-impl<SubHead, SubTail, SuperHead, SuperTail, HeadIndex, TailIndex>
+impl<SubHead: ?Sized, SubTail, SuperHead: ?Sized, SuperTail, HeadIndex, TailIndex>
     SupersetOf<Cons<SubHead, SubTail>, Cons<HeadIndex, TailIndex>> for Cons<SuperHead, SuperTail>
 where
     Cons<SuperHead, SuperTail>: Narrow<SubHead, HeadIndex>,
@@ -481,6 +1204,20 @@ where
             SubTail,
             TailIndex,
         >>::Remainder;
+
+    fn embed(subset: Cons<SubHead, SubTail>) -> Self {
+        match subset {
+            Cons::Head(head) => Narrow::<SubHead, HeadIndex>::inject(head),
+            Cons::Tail(tail) => Narrow::<SubHead, HeadIndex>::widen(SupersetOf::embed(tail)),
+        }
+    }
+
+    fn subset(self) -> Result<Cons<SubHead, SubTail>, Self::Remainder> {
+        match Narrow::<SubHead, HeadIndex>::narrow(self) {
+            Ok(head) => Ok(Cons::Head(head)),
+            Err(remainder) => remainder.subset().map(Cons::Tail),
+        }
+    }
 }
 
 fn _superset_test() {
@@ -0,0 +1,95 @@
+/// A visitor which is applied to the active variant of a `OneOf`
+/// by shared reference through `OneOf::visit`. Implementing this
+/// generically for every type which meets some bound allows any
+/// `OneOf` whose variants all meet that bound to be visited:
+///
+/// ```
+/// use terrors::{OneOf, Visitor};
+///
+/// trait Retryable {
+///     fn retryable(&self) -> bool;
+/// }
+///
+/// struct Timeout;
+/// struct Validation;
+///
+/// impl Retryable for Timeout {
+///     fn retryable(&self) -> bool {
+///         true
+///     }
+/// }
+///
+/// impl Retryable for Validation {
+///     fn retryable(&self) -> bool {
+///         false
+///     }
+/// }
+///
+/// struct IsRetryable;
+///
+/// impl<T: Retryable> Visitor<T> for IsRetryable {
+///     type Output = bool;
+///
+///     fn visit(self, value: &T) -> bool {
+///         value.retryable()
+///     }
+/// }
+///
+/// let o: OneOf<(Timeout, Validation)> = OneOf::new(Timeout);
+///
+/// assert!(o.visit(IsRetryable));
+/// ```
+pub trait Visitor<T: ?Sized> {
+    type Output;
+
+    fn visit(self, value: &T) -> Self::Output;
+}
+
+/// A visitor which is applied to the active variant of a `OneOf`
+/// by mutable reference through `OneOf::visit_mut`.
+pub trait VisitorMut<T: ?Sized> {
+    type Output;
+
+    fn visit_mut(self, value: &mut T) -> Self::Output;
+}
+
+/// A visitor which consumes the active variant of a `OneOf`
+/// through `OneOf::visit_owned`.
+pub trait VisitorOwned<T> {
+    type Output;
+
+    fn visit_owned(self, value: T) -> Self::Output;
+}
+
+/// A projection of a reference to the active variant of a `OneOf`
+/// to a reference to some shared `Target` type, usually a trait
+/// object, through `OneOf::project`. Implementing this generically
+/// for every type which implements some trait allows any `OneOf`
+/// whose variants all implement that trait to be viewed through it:
+///
+/// ```
+/// use std::fmt::Display;
+///
+/// use terrors::{OneOf, Projection};
+///
+/// struct AsDisplay;
+///
+/// impl<T: Display + 'static> Projection<T> for AsDisplay {
+///     type Target = dyn Display;
+///
+///     fn project(value: &T) -> &(dyn Display + 'static) {
+///         value
+///     }
+/// }
+///
+/// let o: OneOf<(u32, String)> = OneOf::new(5_u32);
+///
+/// let display: &dyn Display = o.project::<AsDisplay, _>();
+///
+/// assert_eq!(display.to_string(), "5");
+/// ```
+pub trait Projection<T: ?Sized> {
+    type Target: ?Sized;
+
+    fn project(value: &T) -> &Self::Target;
+}
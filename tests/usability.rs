@@ -1,3 +1,5 @@
+#![allow(clippy::let_unit_value, clippy::type_complexity)]
+
 use terrors::OneOf;
 
 #[derive(Debug)]
@@ -106,7 +108,7 @@ fn debug() {
     println!("{}", o_1);
 
     type E = io::Error;
-    let e = io::Error::new(io::ErrorKind::Other, "wuaaaaahhhzzaaaaaaaa");
+    let e = io::Error::other("wuaaaaahhhzzaaaaaaaa");
 
     let o_2: OneOf<(E,)> = OneOf::new(e);
 
@@ -164,3 +166,1345 @@ fn multi_narrow() {
         }
     }
 }
+
+#[test]
+fn split() {
+    use terrors::{E2, E3};
+
+    struct Timeout {}
+    struct Backoff {}
+
+    let o_1: OneOf<(u8, Backoff, u16, Timeout)> = OneOf::new(Timeout {});
+
+    match o_1.split::<(Timeout, Backoff), _>() {
+        E3::A(Timeout {}) => {}
+        E3::B(Backoff {}) => unreachable!(),
+        E3::C(_rest) => unreachable!(),
+    }
+
+    let o_2: OneOf<(u8, Backoff, u16, Timeout)> = OneOf::new(5_u16);
+
+    match o_2.split::<(Timeout, Backoff), _>() {
+        E3::A(Timeout {}) => unreachable!(),
+        E3::B(Backoff {}) => unreachable!(),
+        E3::C(rest) => {
+            let rest: OneOf<(u8, u16)> = rest;
+            assert_eq!(rest.narrow::<u16, _>().ok(), Some(5));
+        }
+    }
+
+    let o_3: OneOf<(u8, u16)> = OneOf::new(5_u8);
+
+    match o_3.split::<(u8,), _>() {
+        E2::A(byte) => assert_eq!(byte, 5),
+        E2::B(_rest) => unreachable!(),
+    }
+}
+
+#[test]
+fn fully_handled() {
+    use std::convert::Infallible;
+    use terrors::UnwrapInfallible;
+
+    fn fallible(fail: bool) -> Result<u8, OneOf<(Timeout, NotEnoughMemory)>> {
+        if fail {
+            Err(OneOf::new(Timeout))
+        } else {
+            Ok(7)
+        }
+    }
+
+    fn handled(fail: bool) -> Result<u8, OneOf<()>> {
+        fallible(fail).or_else(|err| {
+            let err = match err.narrow::<Timeout, _>() {
+                Ok(Timeout) => return Ok(0),
+                Err(rest) => rest,
+            };
+            match err.narrow::<NotEnoughMemory, _>() {
+                Ok(NotEnoughMemory) => Ok(1),
+                Err(none) => Err(none),
+            }
+        })
+    }
+
+    assert_eq!(handled(false).unwrap_infallible(), 7);
+    assert_eq!(handled(true).unwrap_infallible(), 0);
+
+    let infallible: Result<u8, Infallible> = Ok(3);
+    let as_one_of: Result<u8, OneOf<()>> = infallible.map_err(OneOf::from);
+    let _: Result<u8, Infallible> = as_one_of.map_err(Infallible::from);
+}
+
+#[test]
+fn map_variant() {
+    use std::io;
+
+    #[derive(Debug, PartialEq)]
+    struct StorageError(String);
+
+    #[derive(Debug, PartialEq)]
+    struct Corrupted;
+
+    let o_1: OneOf<(Timeout, io::Error, u8)> = OneOf::new(io::Error::other("disk on fire"));
+    let o_2: OneOf<(Timeout, StorageError, u8)> =
+        o_1.map_variant(|e: io::Error| StorageError(e.to_string()));
+    assert_eq!(
+        o_2.narrow::<StorageError, _>().unwrap(),
+        StorageError("disk on fire".into())
+    );
+
+    let o_3: OneOf<(Timeout, io::Error, u8)> = OneOf::new(5_u8);
+    let o_4 = o_3.map_variant::<io::Error, StorageError, _, _>(|e| StorageError(e.to_string()));
+    let _: OneOf<(Timeout, StorageError, u8)> = o_4;
+
+    let o_5: OneOf<(u16, io::Error, u8)> = OneOf::new(io::Error::other("merge me"));
+    let o_6: OneOf<(u16, u8)> = o_5.merge_variant(|_: io::Error| 3_u8);
+    assert_eq!(o_6.narrow::<u8, _>().unwrap(), 3);
+
+    let o_7: OneOf<(Timeout, io::Error)> = OneOf::new(io::Error::other("corrupt"));
+    let o_8: OneOf<(Corrupted, Timeout, StorageError)> = o_7.try_map_variant(
+        |e: io::Error| -> Result<StorageError, OneOf<(Corrupted,)>> {
+            if e.to_string() == "corrupt" {
+                Err(OneOf::new(Corrupted))
+            } else {
+                Ok(StorageError(e.to_string()))
+            }
+        },
+    );
+    assert_eq!(o_8.narrow::<Corrupted, _>().unwrap(), Corrupted);
+
+    let o_9: OneOf<(Timeout, io::Error)> = OneOf::new(io::Error::other("fine"));
+    let o_10: OneOf<(Corrupted, Timeout, StorageError)> = o_9.try_map_variant(
+        |e: io::Error| -> Result<StorageError, OneOf<(Corrupted,)>> {
+            Ok(StorageError(e.to_string()))
+        },
+    );
+    assert_eq!(
+        o_10.narrow::<StorageError, _>().unwrap(),
+        StorageError("fine".into())
+    );
+}
+
+#[test]
+fn map_variant_async() {
+    use std::future::Future;
+    use std::io;
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    #[derive(Debug, PartialEq)]
+    struct StorageError(String);
+
+    let o_1: OneOf<(Timeout, io::Error)> = OneOf::new(io::Error::other("async"));
+    let fut = o_1.try_map_variant_async(|e: io::Error| async move {
+        Ok::<_, OneOf<(NotEnoughMemory,)>>(StorageError(e.to_string()))
+    });
+
+    let mut cx = Context::from_waker(Waker::noop());
+    let Poll::Ready(o_2) = pin!(fut).poll(&mut cx) else {
+        unreachable!()
+    };
+    let o_2: OneOf<(StorageError, Timeout, NotEnoughMemory)> = o_2;
+
+    assert_eq!(
+        o_2.narrow::<StorageError, _>().unwrap(),
+        StorageError("async".into())
+    );
+}
+
+#[test]
+fn flatten() {
+    use terrors::FlattenErr;
+
+    let o_1: OneOf<(OneOf<(u8, u16)>, u32)> = OneOf::new(OneOf::<(u8, u16)>::new(5_u16));
+    let o_2: OneOf<(u8, u16, u32)> = o_1.flatten();
+    assert_eq!(o_2.narrow::<u16, _>().unwrap(), 5);
+
+    let o_3: OneOf<(OneOf<(u8, u16)>, u32)> = OneOf::new(7_u32);
+    let o_4: OneOf<(u32, u16, u8)> = o_3.flatten();
+    assert_eq!(o_4.narrow::<u32, _>().unwrap(), 7);
+
+    // duplicates across nested sets are merged
+    let o_5: OneOf<(OneOf<(u8, u16)>, u8, OneOf<(u16, u32)>)> =
+        OneOf::new(OneOf::<(u16, u32)>::new(9_u16));
+    let o_6: OneOf<(u8, u16, u32)> = o_5.flatten();
+    assert_eq!(o_6.narrow::<u16, _>().unwrap(), 9);
+
+    let r_1: Result<Result<(), OneOf<(u8, u16)>>, OneOf<(u16, u32)>> = Ok(Err(OneOf::new(1_u8)));
+    let r_2: Result<(), OneOf<(u8, u16, u32)>> = r_1.flatten_err();
+    assert_eq!(r_2.unwrap_err().narrow::<u8, _>().unwrap(), 1);
+
+    let r_3: Result<Result<(), Timeout>, OneOf<(u16, u32)>> = Err(OneOf::new(2_u32));
+    let r_4: Result<(), OneOf<(Timeout, u16, u32)>> = r_3.flatten_err();
+    assert_eq!(r_4.unwrap_err().narrow::<u32, _>().unwrap(), 2);
+
+    let r_5: Result<Result<u8, Timeout>, NotEnoughMemory> = Ok(Ok(3));
+    let r_6: Result<u8, OneOf<(NotEnoughMemory, Timeout)>> = r_5.flatten_err();
+    assert_eq!(r_6.unwrap(), 3);
+}
+
+#[test]
+fn visitor() {
+    use terrors::{Visitor, VisitorMut, VisitorOwned};
+
+    trait Severity {
+        fn severity(&self) -> u8;
+    }
+
+    impl Severity for Timeout {
+        fn severity(&self) -> u8 {
+            1
+        }
+    }
+
+    impl Severity for NotEnoughMemory {
+        fn severity(&self) -> u8 {
+            5
+        }
+    }
+
+    impl Severity for String {
+        fn severity(&self) -> u8 {
+            self.len() as u8
+        }
+    }
+
+    struct SeverityVisitor;
+
+    impl<T: Severity> Visitor<T> for SeverityVisitor {
+        type Output = u8;
+
+        fn visit(self, value: &T) -> u8 {
+            value.severity()
+        }
+    }
+
+    struct Label<'a>(&'a mut Vec<&'static str>);
+
+    impl<T> Visitor<T> for Label<'_> {
+        type Output = ();
+
+        fn visit(self, _: &T) {
+            self.0.push(std::any::type_name::<T>());
+        }
+    }
+
+    struct Truncate;
+
+    impl VisitorMut<String> for Truncate {
+        type Output = ();
+
+        fn visit_mut(self, value: &mut String) {
+            value.truncate(2);
+        }
+    }
+
+    impl VisitorMut<Timeout> for Truncate {
+        type Output = ();
+
+        fn visit_mut(self, _: &mut Timeout) {}
+    }
+
+    struct IntoSeverity;
+
+    impl<T: Severity> VisitorOwned<T> for IntoSeverity {
+        type Output = u8;
+
+        fn visit_owned(self, value: T) -> u8 {
+            value.severity()
+        }
+    }
+
+    let o_1: OneOf<(Timeout, NotEnoughMemory)> = OneOf::new(NotEnoughMemory);
+    assert_eq!(o_1.visit(SeverityVisitor), 5);
+
+    let mut labels = vec![];
+    o_1.visit(Label(&mut labels));
+    assert!(labels[0].ends_with("NotEnoughMemory"));
+
+    let mut o_2: OneOf<(Timeout, String)> = OneOf::new("hello".to_string());
+    o_2.visit_mut(Truncate);
+    assert_eq!(o_2.visit(SeverityVisitor), 2);
+    assert_eq!(o_2.visit_owned(IntoSeverity), 2);
+}
+
+#[test]
+fn dyn_projection() {
+    use std::error::Error;
+    use std::fmt;
+    use std::io;
+
+    use terrors::Projection;
+
+    #[derive(Debug)]
+    struct Unreadable;
+
+    impl fmt::Display for Unreadable {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "unreadable")
+        }
+    }
+
+    impl Error for Unreadable {}
+
+    let o_1: OneOf<(io::Error, Unreadable)> = OneOf::new(Unreadable);
+
+    let dyn_error: &dyn Error = o_1.as_dyn_error();
+    assert_eq!(dyn_error.to_string(), "unreadable");
+    assert!(dyn_error.downcast_ref::<Unreadable>().is_some());
+
+    let boxed: Box<dyn Error + Send + Sync> = o_1.into_box_dyn_error();
+    assert!(boxed.downcast::<Unreadable>().is_ok());
+
+    struct AsDisplaySend;
+
+    impl<T: fmt::Display + Send + 'static> Projection<T> for AsDisplaySend {
+        type Target = dyn fmt::Display + Send;
+
+        fn project(value: &T) -> &(dyn fmt::Display + Send + 'static) {
+            value
+        }
+    }
+
+    let o_2: OneOf<(u32, String)> = OneOf::new("projected".to_string());
+    let display: &(dyn fmt::Display + Send) = o_2.project::<AsDisplaySend, _>();
+    assert_eq!(display.to_string(), "projected");
+}
+
+#[test]
+fn delegation() {
+    use std::future::{ready, Future, Ready};
+    use std::io::{self, BufRead, Cursor, Read, Write};
+    use std::pin::{pin, Pin};
+    use std::task::{Context, Poll, Waker};
+
+    let mut reader: OneOf<(&'static [u8], Cursor<Vec<u8>>)> =
+        OneOf::new(Cursor::new(b"line one\nline two".to_vec()));
+    let mut first_line = String::new();
+    reader.read_line(&mut first_line).unwrap();
+    assert_eq!(first_line, "line one\n");
+    let mut rest = String::new();
+    reader.read_to_string(&mut rest).unwrap();
+    assert_eq!(rest, "line two");
+
+    let mut writer: OneOf<(Vec<u8>, io::Sink)> = OneOf::new(Vec::<u8>::new());
+    writer.write_all(b"written").unwrap();
+    writer.flush().unwrap();
+    assert_eq!(writer.narrow::<Vec<u8>, _>().unwrap(), b"written");
+
+    let iter: OneOf<(std::ops::Range<u32>, std::vec::IntoIter<u32>)> =
+        OneOf::new(vec![3, 4].into_iter());
+    assert_eq!(iter.size_hint(), (2, Some(2)));
+    assert_eq!(iter.sum::<u32>(), 7);
+
+    let s: OneOf<(String, &'static str)> = OneOf::new("borrowed");
+    assert_eq!(s.as_ref(), "borrowed");
+
+    type Boxed = Pin<Box<dyn Future<Output = u8>>>;
+    let fut: OneOf<(Ready<u8>, Boxed)> = OneOf::new(Box::pin(async { 9_u8 }) as Boxed);
+    let mut cx = Context::from_waker(Waker::noop());
+    assert_eq!(pin!(fut).poll(&mut cx), Poll::Ready(9));
+
+    let fut: OneOf<(Ready<u8>, Boxed)> = OneOf::new(ready(1));
+    assert_eq!(pin!(fut).poll(&mut cx), Poll::Ready(1));
+}
+
+#[test]
+fn erased_boxes() {
+    use std::any::Any;
+    use std::error::Error;
+    use std::io;
+
+    let boxed: Box<dyn Any> = Box::new(5_u16);
+    let address = &*boxed as *const dyn Any as *const u8;
+    let o_1: OneOf<(u8, u16)> = OneOf::try_from_any(boxed).unwrap();
+    let any = o_1.into_any();
+    assert_eq!(&*any as *const dyn Any as *const u8, address);
+    assert_eq!(any.downcast_ref::<u16>(), Some(&5));
+
+    let rejected = OneOf::<(u8, u32)>::try_from_any(any).unwrap_err();
+    assert!(rejected.is::<u16>());
+
+    let dyn_error: Box<dyn Error + Send + Sync> = Box::new(io::Error::other("plugin"));
+    let o_2: OneOf<(io::Error, std::fmt::Error)> = OneOf::try_from_dyn_error(dyn_error).unwrap();
+    assert_eq!(o_2.to_string(), "plugin");
+
+    let dyn_error: Box<dyn Error + Send + Sync> = Box::new(std::fmt::Error);
+    let rejected = OneOf::<(io::Error,)>::try_from_dyn_error(dyn_error).unwrap_err();
+    assert!(rejected.is::<std::fmt::Error>());
+
+    let o_3: OneOf<(String, u32)> = OneOf::from_box(Box::new(3_u32));
+    assert_eq!(o_3.narrow::<u32, _>().unwrap(), 3);
+}
+
+#[test]
+fn send_sync() {
+    let o: OneOf<(u32, String)> = OneOf::new("yooo".to_string());
+
+    let handle = std::thread::spawn(move || o.narrow::<String, _>().unwrap());
+
+    assert_eq!(handle.join().unwrap(), "yooo");
+
+    let shared: std::sync::Arc<OneOf<(u32, String)>> = std::sync::Arc::new(OneOf::new(5_u32));
+    let cloned = shared.clone();
+
+    std::thread::spawn(move || assert_eq!(format!("{cloned:?}"), "5"))
+        .join()
+        .unwrap();
+
+    // the active variant keeps its value when moved into a subset's remainder
+    let o: OneOf<(u8, u16, u32, u64, u128)> = OneOf::new(5_u32);
+    let remainder: OneOf<(u16, u32, u64)> = o.subset::<(u8, u128), _>().unwrap_err();
+    assert_eq!(remainder.narrow::<u32, _>().unwrap(), 5);
+}
+
+#[test]
+fn borrowed_errors() {
+    use terrors::E2;
+
+    #[derive(Debug, PartialEq)]
+    struct ParseError<'src> {
+        rest: &'src str,
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct EmptyInput;
+
+    fn parse(input: &str) -> Result<u32, OneOf<(ParseError<'_>, EmptyInput)>> {
+        if input.is_empty() {
+            return Err(OneOf::new(EmptyInput));
+        }
+
+        let digits = input.chars().take_while(char::is_ascii_digit).count();
+        if digits < input.len() {
+            return Err(OneOf::new(ParseError {
+                rest: &input[digits..],
+            }));
+        }
+
+        Ok(input.parse().unwrap())
+    }
+
+    let input = String::from("12ab");
+
+    let err = parse(&input).unwrap_err();
+    assert_eq!(err.narrow::<ParseError, _>().unwrap().rest, "ab");
+
+    let err = parse(&input).unwrap_err();
+    let broadened: OneOf<(EmptyInput, u64, ParseError)> = err.broaden();
+    let subset: Result<OneOf<(ParseError,)>, OneOf<(EmptyInput, u64)>> = broadened.subset();
+    assert_eq!(subset.unwrap().take().rest, "ab");
+
+    match parse("").unwrap_err().to_enum() {
+        E2::A(ParseError { .. }) => unreachable!(),
+        E2::B(EmptyInput) => {}
+    }
+
+    assert_eq!(parse("12").unwrap(), 12);
+}
+
+#[test]
+fn unsized_variants() {
+    use std::error::Error;
+    use std::io;
+
+    use terrors::{E1, E2};
+
+    let o: OneOf<(u8, str)> = OneOf::from_box(Box::<str>::from("hello"));
+    let remainder = o.narrow::<u8, _>().unwrap_err();
+    assert_eq!(&*remainder, "hello");
+
+    let o: OneOf<(u8, [u8])> = OneOf::from_box(Box::<[u8]>::from(&b"abc"[..]));
+    match o.as_enum() {
+        E2::A(_) => unreachable!(),
+        E2::B(bytes) => assert_eq!(bytes, b"abc"),
+    }
+
+    type Catchall = dyn Error + Send + Sync;
+
+    let io_error: Box<Catchall> = Box::new(io::Error::other("yooo"));
+    let o: OneOf<(Timeout, Catchall)> = OneOf::from_box(io_error);
+
+    assert!(format!("{o:?}").contains("yooo"));
+
+    let io_error: Box<Catchall> = Box::new(io::Error::other("yooo"));
+    let searchable: OneOf<(std::fmt::Error, Catchall)> = OneOf::from_box(io_error);
+    assert!(searchable.find_source::<io::Error>().is_some());
+
+    let broadened: OneOf<(NotEnoughMemory, Timeout, Catchall)> = o.broaden();
+    let catchall: Box<Catchall> = broadened.narrow_boxed().unwrap();
+    assert!(catchall.downcast_ref::<io::Error>().is_some());
+
+    let o: OneOf<(Timeout, Catchall)> = OneOf::from_box(catchall);
+    match o.to_enum() {
+        E2::A(Timeout) => unreachable!(),
+        E2::B(catchall) => assert_eq!(catchall.to_string(), "yooo"),
+    }
+
+    let o: OneOf<(str,)> = OneOf::from_box(Box::<str>::from("alone"));
+    let E1::A(boxed) = o.to_enum();
+    assert_eq!(&*boxed, "alone");
+}
+
+#[test]
+fn open_set() {
+    use std::fmt;
+    use std::io;
+
+    use terrors::{Other, E3};
+
+    #[derive(Debug, PartialEq)]
+    struct NotFound;
+
+    impl fmt::Display for NotFound {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "not found")
+        }
+    }
+
+    impl std::error::Error for NotFound {}
+
+    #[derive(Debug)]
+    struct Corrupted;
+
+    impl fmt::Display for Corrupted {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "corrupted")
+        }
+    }
+
+    impl std::error::Error for Corrupted {}
+
+    // a newer version of the library has started returning `Corrupted`
+    fn lookup(corrupted: bool) -> Result<(), OneOf<(NotFound, Corrupted, io::Error)>> {
+        if corrupted {
+            Err(OneOf::new(Corrupted))
+        } else {
+            Err(OneOf::new(NotFound))
+        }
+    }
+
+    // but its public signature is unchanged
+    fn public_lookup(corrupted: bool) -> Result<(), OneOf<(NotFound, io::Error, Other)>> {
+        lookup(corrupted).map_err(OneOf::broaden_open)
+    }
+
+    let err = public_lookup(false).unwrap_err();
+    assert_eq!(err.narrow::<NotFound, _>().unwrap(), NotFound);
+
+    match public_lookup(true).unwrap_err().to_enum() {
+        E3::A(NotFound) | E3::B(_) => unreachable!(),
+        E3::C(other) => assert!(other.is::<Corrupted>()),
+    }
+
+    let o: OneOf<(NotFound, Other)> = OneOf::absorb(NotFound);
+    assert!(o.narrow::<NotFound, _>().is_ok());
+
+    let o: OneOf<(NotFound, Other)> = OneOf::absorb(Corrupted);
+    let other = o.narrow::<Other, _>().unwrap();
+    assert!(other.downcast::<Corrupted>().is_ok());
+
+    // an `Other` is unwrapped when absorbed, so its error can still be placed precisely
+    let o: OneOf<(Corrupted, Other)> = OneOf::absorb(Other::new(NotFound));
+    let o: OneOf<(NotFound, Other)> = o.broaden_open();
+    assert!(o.narrow::<NotFound, _>().is_ok());
+}
+
+#[test]
+fn shared() {
+    use std::io;
+
+    use terrors::{SharedOneOf, E2};
+
+    // `io::Error` is not `Clone`, but a `SharedOneOf` of it is
+    let shared: SharedOneOf<(Timeout, io::Error)> =
+        SharedOneOf::new(OneOf::new(io::Error::other("yooo")));
+
+    let waiters: Vec<_> = (0..4)
+        .map(|_| {
+            let shared = shared.clone();
+            std::thread::spawn(move || {
+                assert!(shared.narrow_ref::<Timeout, _>().is_none());
+                match shared.as_enum() {
+                    E2::A(Timeout) => unreachable!(),
+                    E2::B(error) => error.to_string(),
+                }
+            })
+        })
+        .collect();
+
+    for waiter in waiters {
+        assert_eq!(waiter.join().unwrap(), "yooo");
+    }
+
+    let extra = shared.clone();
+    assert!(extra.ptr_eq(&shared));
+
+    let shared = shared.try_unwrap().err().unwrap();
+    drop(extra);
+
+    let owned = shared.try_unwrap().unwrap();
+    assert!(owned.narrow::<io::Error, _>().is_ok());
+}
+
+#[test]
+fn deep_narrowing() {
+    use std::error::Error;
+    use std::fmt;
+    use std::io;
+
+    #[derive(Debug, PartialEq)]
+    struct Timeout;
+
+    impl fmt::Display for Timeout {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "timed out")
+        }
+    }
+
+    impl Error for Timeout {}
+
+    #[derive(Debug)]
+    struct Tls(Timeout);
+
+    impl fmt::Display for Tls {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "tls handshake failed")
+        }
+    }
+
+    impl Error for Tls {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    let o: OneOf<(io::Error, Timeout)> = OneOf::new(io::Error::other(Tls(Timeout)));
+    assert_eq!(o.find_source::<Timeout>(), Some(&Timeout));
+    assert!(o.find_source::<Tls>().is_some());
+    assert!(o.find_source::<fmt::Error>().is_none());
+
+    let o: OneOf<(io::Error, Timeout)> = OneOf::new(Timeout);
+    assert_eq!(o.find_source::<Timeout>(), Some(&Timeout));
+
+    #[cfg(feature = "io")]
+    {
+        let o = terrors::io::classify(io::Error::other(Tls(Timeout)));
+        assert!(o.find_source::<Tls>().is_some());
+        assert_eq!(o.find_source::<Timeout>(), Some(&Timeout));
+    }
+
+    // the active variant of a nested `OneOf` is searched too
+    let o: OneOf<(OneOf<(Timeout, Tls)>, Tls)> = OneOf::new(OneOf::new(Timeout));
+    assert_eq!(o.find_source::<Timeout>(), Some(&Timeout));
+    assert!(o.find_source::<Tls>().is_none());
+
+    #[derive(Debug)]
+    struct Handshake(OneOf<(Timeout, Tls)>);
+
+    impl fmt::Display for Handshake {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "handshake failed")
+        }
+    }
+
+    impl Error for Handshake {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    let o: OneOf<(Handshake, Tls)> = OneOf::new(Handshake(OneOf::new(Timeout)));
+    assert_eq!(o.find_source::<Timeout>(), Some(&Timeout));
+
+    // the hook only applies to `find_source`, not to `Error::source`
+    let handshake = Handshake(OneOf::new(Tls(Timeout)));
+    let source = handshake.source().unwrap().source().unwrap();
+    assert!(source.downcast_ref::<Timeout>().is_some());
+
+    type Inner = OneOf<(Timeout, String)>;
+    type Outer = OneOf<(u32, OneOf<(u8, Inner)>)>;
+
+    let o: Outer = OneOf::new(OneOf::new(Inner::new(Timeout)));
+    assert_eq!(o.narrow_deep::<Timeout, _>().unwrap(), Timeout);
+
+    let o: Outer = OneOf::new(OneOf::new(Inner::new("yooo".to_string())));
+    let o = o.narrow_deep::<Timeout, _>().unwrap_err();
+    let o = o.narrow_deep::<u32, _>().unwrap_err();
+    assert_eq!(o.narrow_deep::<String, _>().unwrap(), "yooo");
+}
+
+#[test]
+fn type_information() {
+    use std::fmt;
+
+    #[derive(Debug)]
+    struct Timeout;
+
+    impl fmt::Display for Timeout {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "timed out")
+        }
+    }
+
+    let o: OneOf<(u32, String)> = OneOf::new(5_u32);
+    assert_eq!(format!("{o:?}"), "5");
+    assert_eq!(format!("{o:#?}"), "OneOf<(u32, String)>::u32(5)");
+
+    let o: OneOf<(Vec<u8>, Timeout)> = OneOf::new(Timeout);
+    assert_eq!(
+        format!("{o:#?}"),
+        "OneOf<(Vec<u8>, Timeout)>::Timeout(Timeout)"
+    );
+
+    let o: OneOf<(String, Timeout)> = OneOf::new(Timeout);
+    assert_eq!(o.display_with_type().to_string(), "Timeout: timed out");
+    assert!(o.type_name().ends_with("Timeout"));
+}
+
+#[cfg(feature = "io")]
+#[test]
+fn io_classification() {
+    use std::io::{self, ErrorKind};
+
+    use terrors::io::{
+        classify, classify_into, AlreadyExists, ConnectionRefused, Interrupted, NotFound, OtherIo,
+        PermissionDenied, TimedOut, WouldBlock,
+    };
+
+    let not_found = classify(io::Error::new(ErrorKind::NotFound, "missing"));
+    let not_found = not_found.narrow::<NotFound, _>().unwrap();
+    assert_eq!(not_found.to_string(), "missing");
+    assert_eq!(not_found.as_io_error().kind(), ErrorKind::NotFound);
+
+    let other = classify(io::Error::new(ErrorKind::BrokenPipe, "pipe"));
+    let other = other.narrow::<OtherIo, _>().unwrap();
+    assert_eq!(other.kind(), ErrorKind::BrokenPipe);
+
+    let timed_out = classify(io::Error::from(ErrorKind::TimedOut));
+    let rest = timed_out.narrow::<NotFound, _>().unwrap_err();
+    let broadened: OneOf<(
+        TimedOut,
+        AlreadyExists,
+        PermissionDenied,
+        String,
+        Interrupted,
+        WouldBlock,
+        OtherIo,
+    )> = rest.broaden();
+    assert!(broadened.narrow::<TimedOut, _>().is_ok());
+
+    let refused: OneOf<(ConnectionRefused, TimedOut, OtherIo)> =
+        classify_into(io::Error::from(ErrorKind::ConnectionRefused));
+    assert!(refused.narrow::<ConnectionRefused, _>().is_ok());
+
+    fn reraise(error: NotFound) -> io::Result<()> {
+        Err(error)?
+    }
+
+    let error = classify(io::Error::from(ErrorKind::NotFound))
+        .narrow::<NotFound, _>()
+        .unwrap();
+    assert_eq!(reraise(error).unwrap_err().kind(), ErrorKind::NotFound);
+}
+
+#[cfg(feature = "io")]
+#[test]
+fn fs_wrappers() {
+    use terrors::io::{AlreadyExists, IsADirectory, NotADirectory, NotFound};
+
+    let dir = std::env::temp_dir().join(format!("terrors-fs-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    terrors::fs::create_dir(&dir).unwrap();
+
+    let already_exists = terrors::fs::create_dir(&dir).unwrap_err();
+    let already_exists = already_exists.narrow::<AlreadyExists, _>().unwrap();
+    assert_eq!(already_exists.path(), Some(dir.as_path()));
+
+    let file = dir.join("data");
+    terrors::fs::write(&file, b"hello").unwrap();
+    assert_eq!(terrors::fs::read(&file).unwrap(), b"hello");
+    assert!(terrors::fs::open(&file).is_ok());
+    assert_eq!(terrors::fs::metadata(&file).unwrap().len(), 5);
+
+    let not_a_directory = terrors::fs::read_dir(&file).unwrap_err();
+    assert!(not_a_directory.narrow::<NotADirectory, _>().is_ok());
+
+    let is_a_directory = terrors::fs::read(&dir).unwrap_err();
+    assert!(is_a_directory.narrow::<IsADirectory, _>().is_ok());
+
+    let renamed = dir.join("renamed");
+    terrors::fs::rename(&file, &renamed).unwrap();
+    assert_eq!(terrors::fs::read_dir(&dir).unwrap().count(), 1);
+
+    let not_found = terrors::fs::rename(&file, &renamed).unwrap_err();
+    let not_found = not_found.narrow::<NotFound, _>().unwrap();
+    assert_eq!(not_found.path(), Some(file.as_path()));
+    assert!(not_found
+        .to_string()
+        .starts_with(&file.display().to_string()));
+
+    terrors::fs::remove_file(&renamed).unwrap();
+    let not_found = terrors::fs::remove_file(&renamed).unwrap_err();
+    assert!(not_found.narrow::<NotFound, _>().is_ok());
+
+    std::fs::remove_dir(&dir).unwrap();
+}
+
+#[cfg(feature = "io")]
+#[test]
+fn net_wrappers() {
+    use std::time::Duration;
+
+    use terrors::io::{AddrInUse, ConnectionRefused, TimedOut};
+
+    let listener = terrors::net::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+
+    let in_use = terrors::net::bind(address).unwrap_err();
+    assert!(in_use.narrow::<AddrInUse, _>().is_ok());
+
+    let udp = terrors::net::bind_udp("127.0.0.1:0").unwrap();
+    let in_use = terrors::net::bind_udp(udp.local_addr().unwrap()).unwrap_err();
+    assert!(in_use.narrow::<AddrInUse, _>().is_ok());
+
+    let client = terrors::net::connect(address).unwrap();
+    let (server, _) = listener.accept().unwrap();
+
+    let mut buf = [0; 5];
+    let timed_out =
+        terrors::net::read_timeout(&client, &mut buf, Duration::from_millis(10)).unwrap_err();
+    assert!(timed_out.narrow::<TimedOut, _>().is_ok());
+
+    terrors::net::write_all_timeout(&server, b"hello", Duration::from_secs(1)).unwrap();
+    let read = terrors::net::read_timeout(&client, &mut buf, Duration::from_secs(1)).unwrap();
+    assert_eq!(&buf[..read], &b"hello"[..read]);
+
+    drop(listener);
+    let refused = terrors::net::connect_timeout(&address, Duration::from_secs(1)).unwrap_err();
+    assert!(refused.narrow::<ConnectionRefused, _>().is_ok());
+}
+
+#[test]
+fn common_vocabulary() {
+    use std::time::Duration;
+
+    use terrors::common::{AlreadyExists, Cancelled, NotFound, RetriesExhausted, Timeout};
+
+    struct User;
+    struct Order;
+
+    fn fetch() -> Result<(), OneOf<(NotFound<User>, Timeout)>> {
+        Err(OneOf::new(Timeout::after(Duration::from_millis(50))))
+    }
+
+    fn place() -> Result<(), OneOf<(NotFound<Order>, AlreadyExists<Order>, Timeout, Cancelled)>> {
+        Err(OneOf::new(NotFound::with_key(7)))
+    }
+
+    // both crates use the same `Timeout`, so it is only in the set once
+    let e: OneOf<(
+        NotFound<User>,
+        NotFound<Order>,
+        AlreadyExists<Order>,
+        Timeout,
+        Cancelled,
+    )> = fetch().unwrap_err().broaden();
+    let timeout = e.narrow::<Timeout, _>().unwrap();
+    assert_eq!(timeout, Timeout::after(Duration::from_millis(50)));
+    assert_eq!(timeout.to_string(), "timed out after 50ms");
+
+    let e: OneOf<(
+        NotFound<User>,
+        NotFound<Order>,
+        AlreadyExists<Order>,
+        Timeout,
+        Cancelled,
+    )> = place().unwrap_err().broaden();
+    let not_found = e.narrow::<NotFound<Order>, _>().unwrap();
+    assert_eq!(not_found.key(), Some("7"));
+    assert_eq!(not_found.to_string(), "Order 7 not found");
+    assert_eq!(not_found.clone(), NotFound::with_key("7"));
+
+    assert_eq!(Cancelled.to_string(), "cancelled");
+    assert_eq!(
+        RetriesExhausted::after_attempts(3).to_string(),
+        "retries exhausted after 3 attempts"
+    );
+    assert_eq!(
+        AlreadyExists::<User>::new().to_string(),
+        "User already exists"
+    );
+
+    #[cfg(feature = "io")]
+    {
+        let error = terrors::fs::read("/definitely/not/here").unwrap_err();
+        let error =
+            error.map_variant(|not_found: terrors::io::NotFound| NotFound::<User>::from(not_found));
+        let not_found = error.narrow::<NotFound<User>, _>().unwrap();
+        assert_eq!(not_found.key(), Some("/definitely/not/here"));
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn common_vocabulary_serde() {
+    use std::time::Duration;
+
+    use terrors::common::{AlreadyExists, Cancelled, NotFound, RetriesExhausted, Timeout};
+
+    struct Order;
+
+    fn round_trip<T>(value: &T) -> T
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned,
+    {
+        serde_json::from_str(&serde_json::to_string(value).unwrap()).unwrap()
+    }
+
+    let timeout = Timeout::after(Duration::from_millis(50));
+    assert_eq!(round_trip(&timeout), timeout);
+    assert_eq!(round_trip(&Timeout::new()), Timeout::new());
+    assert_eq!(round_trip(&Cancelled), Cancelled);
+
+    let exhausted = RetriesExhausted::after_attempts(3);
+    assert_eq!(round_trip(&exhausted), exhausted);
+
+    let not_found = NotFound::<Order>::with_key(7);
+    assert_eq!(round_trip(&not_found), not_found);
+    assert_eq!(serde_json::to_string(&not_found).unwrap(), r#"{"key":"7"}"#);
+
+    let already_exists = AlreadyExists::<Order>::new();
+    assert_eq!(round_trip(&already_exists), already_exists);
+}
+
+#[test]
+fn retry_combinator() {
+    use std::time::Duration;
+
+    use terrors::common::{RetriesExhausted, Timeout};
+    use terrors::retry::{ManualClock, RetryPolicy};
+
+    #[derive(Debug)]
+    struct ConnectionReset;
+
+    let clock = ManualClock::new();
+    let policy = RetryPolicy::exponential(4, Duration::from_millis(10), Duration::from_millis(25))
+        .with_clock(&clock);
+
+    let mut attempts = 0;
+    let result: Result<u32, OneOf<(NotEnoughMemory, RetriesExhausted)>> =
+        policy.retry_on::<(Timeout, ConnectionReset)>().run(|| {
+            attempts += 1;
+            if attempts < 3 {
+                Err(OneOf::<(Timeout, NotEnoughMemory, ConnectionReset)>::new(
+                    Timeout::new(),
+                ))
+            } else {
+                Ok(attempts)
+            }
+        });
+    assert_eq!(result.unwrap(), 3);
+    assert_eq!(
+        clock.sleeps(),
+        [Duration::from_millis(10), Duration::from_millis(20)]
+    );
+
+    type Exhausted = (NotEnoughMemory, RetriesExhausted);
+
+    let mut attempts = 0;
+    let exhausted: Result<(), OneOf<Exhausted>> =
+        policy.retry_on::<(Timeout, ConnectionReset)>().run(|| {
+            attempts += 1;
+            Err(OneOf::<(Timeout, NotEnoughMemory, ConnectionReset)>::new(
+                Timeout::new(),
+            ))
+        });
+    let exhausted = exhausted
+        .unwrap_err()
+        .narrow::<RetriesExhausted, _>()
+        .unwrap();
+    assert_eq!(exhausted.attempts(), Some(4));
+    assert_eq!(attempts, 4);
+    assert_eq!(clock.sleeps()[4], Duration::from_millis(25));
+
+    let mut attempts = 0;
+    let fatal: Result<(), OneOf<Exhausted>> =
+        policy.retry_on::<(Timeout, ConnectionReset)>().run(|| {
+            attempts += 1;
+            Err(OneOf::<(Timeout, NotEnoughMemory, ConnectionReset)>::new(
+                NotEnoughMemory,
+            ))
+        });
+    assert!(fatal.unwrap_err().narrow::<NotEnoughMemory, _>().is_ok());
+    assert_eq!(attempts, 1);
+
+    // the inner retry's `RetriesExhausted` is merged with the outer one's
+    fn timeout() -> OneOf<(Timeout, ConnectionReset)> {
+        OneOf::new(Timeout::new())
+    }
+
+    fn reset() -> OneOf<(Timeout, ConnectionReset)> {
+        OneOf::new(ConnectionReset)
+    }
+
+    type Inner = Result<(), OneOf<(Timeout, RetriesExhausted)>>;
+
+    let inner = |error: fn() -> OneOf<(Timeout, ConnectionReset)>| -> Inner {
+        let policy = RetryPolicy::fixed(2, Duration::ZERO).with_clock(ManualClock::new());
+        policy.retry_on::<(ConnectionReset,)>().run(|| Err(error()))
+    };
+
+    let mut attempts = 0;
+    let nested: Result<(), OneOf<(RetriesExhausted,)>> =
+        policy.retry_on::<(Timeout,)>().run(|| {
+            attempts += 1;
+            inner(timeout)
+        });
+    let exhausted = nested.unwrap_err().narrow::<RetriesExhausted, _>().unwrap();
+    assert_eq!(exhausted.attempts(), Some(4));
+    assert_eq!(attempts, 4);
+
+    let nested: Result<(), OneOf<(RetriesExhausted,)>> =
+        policy.retry_on::<(Timeout,)>().run(|| inner(reset));
+    let exhausted = nested.unwrap_err().narrow::<RetriesExhausted, _>().unwrap();
+    assert_eq!(exhausted.attempts(), Some(2));
+
+    let jittered = RetryPolicy::fixed(3, Duration::from_millis(10)).jittered();
+    for retry in 0..10 {
+        assert!(jittered.delay(retry) <= Duration::from_millis(10));
+    }
+}
+
+#[test]
+fn circuit_breaker() {
+    use std::time::Duration;
+
+    use terrors::circuit::{CircuitBreaker, CircuitOpen, CircuitState};
+    use terrors::common::Timeout;
+    use terrors::retry::ManualClock;
+
+    #[derive(Debug)]
+    struct Validation;
+
+    #[derive(Debug)]
+    struct ConnectionRefused;
+
+    type E = OneOf<(Validation, Timeout)>;
+    type Guarded<T = ()> = Result<T, OneOf<(Validation, Timeout, CircuitOpen)>>;
+
+    let clock = ManualClock::new();
+    let breaker = CircuitBreaker::<(Timeout, ConnectionRefused)>::new(2, Duration::from_secs(10))
+        .with_clock(&clock);
+
+    let timeout = || Err::<(), E>(OneOf::new(Timeout::new()));
+    let invalid = || Err::<(), E>(OneOf::new(Validation));
+
+    // validation errors do not count towards opening the circuit
+    let e: Guarded = breaker.call(timeout);
+    assert!(e.unwrap_err().narrow::<Timeout, _>().is_ok());
+    let e: Guarded = breaker.call(invalid);
+    assert!(e.is_err());
+    let e: Guarded = breaker.call(timeout);
+    assert!(e.is_err());
+    assert_eq!(breaker.state(), CircuitState::Closed);
+
+    let e: Guarded = breaker.call(timeout);
+    assert!(e.is_err());
+    assert_eq!(breaker.state(), CircuitState::Open);
+
+    let open: Guarded<u32> = breaker.call(|| Ok::<_, E>(5));
+    let open = open.unwrap_err().narrow::<CircuitOpen, _>().unwrap();
+    assert_eq!(open.retry_after(), Duration::from_secs(10));
+
+    // a failed probe opens the circuit again
+    clock.advance(Duration::from_secs(10));
+    assert_eq!(breaker.state(), CircuitState::HalfOpen);
+    let e: Guarded = breaker.call(timeout);
+    assert!(e.is_err());
+    assert_eq!(breaker.state(), CircuitState::Open);
+
+    // and a successful one closes it
+    clock.advance(Duration::from_secs(10));
+    let five: Guarded<u32> = breaker.call(|| Ok::<_, E>(5));
+    assert_eq!(five.unwrap(), 5);
+    assert_eq!(breaker.state(), CircuitState::Closed);
+
+    let stats = breaker.stats();
+    assert_eq!(stats.successes(), 1);
+    assert_eq!(stats.rejected(), 1);
+    assert_eq!(stats.trips(), 4);
+    assert_eq!(stats.failures(), 5);
+    assert_eq!(stats.failures_of::<Timeout>(), 4);
+    assert_eq!(stats.failures_of::<Validation>(), 1);
+    assert_eq!(stats.failures_of::<ConnectionRefused>(), 0);
+
+    let breaker = &CircuitBreaker::<(Timeout,)>::new(1, Duration::from_secs(10)).with_clock(&clock);
+
+    // an overlapping call which started before the circuit opened cannot close it
+    let overlapping = |outcome: fn() -> Result<(), E>| {
+        move || {
+            let e: Guarded = breaker.call(timeout);
+            assert!(e.is_err());
+            assert_eq!(breaker.state(), CircuitState::Open);
+            outcome()
+        }
+    };
+    let e: Guarded = breaker.call(overlapping(timeout));
+    assert!(e.is_err());
+    assert_eq!(breaker.state(), CircuitState::Open);
+
+    clock.advance(Duration::from_secs(10));
+    let ok: Guarded = breaker.call(|| Ok::<_, E>(()));
+    assert!(ok.is_ok());
+    let e: Guarded = breaker.call(overlapping(invalid));
+    assert!(e.is_err());
+    assert_eq!(breaker.state(), CircuitState::Open);
+    assert_eq!(breaker.stats().trips(), 3);
+
+    // a probe which panics opens the circuit again
+    clock.advance(Duration::from_secs(10));
+    let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| -> Guarded {
+        breaker.call(|| -> Result<(), E> { std::panic::resume_unwind(Box::new("probe")) })
+    }));
+    assert!(panicked.is_err());
+    assert_eq!(breaker.state(), CircuitState::Open);
+
+    clock.advance(Duration::from_secs(10));
+    let ok: Guarded = breaker.call(|| Ok::<_, E>(()));
+    assert!(ok.is_ok());
+    assert_eq!(breaker.state(), CircuitState::Closed);
+
+    // the `CircuitOpen` of an inner breaker is merged with the outer one's
+    let outer = CircuitBreaker::<(Validation,)>::new(1, Duration::from_secs(10)).with_clock(&clock);
+    let inner = || -> Guarded { breaker.call(timeout) };
+    let nested = || -> Guarded { outer.call(inner) };
+
+    assert!(nested().unwrap_err().narrow::<Timeout, _>().is_ok());
+    assert_eq!(breaker.state(), CircuitState::Open);
+    let open = nested().unwrap_err().narrow::<CircuitOpen, _>().unwrap();
+    assert_eq!(open.retry_after(), Duration::from_secs(10));
+    assert_eq!(outer.state(), CircuitState::Closed);
+    assert_eq!(outer.stats().failures_of::<CircuitOpen>(), 1);
+}
+
+#[cfg(feature = "futures")]
+#[test]
+fn futures_extensions() {
+    use std::future::{pending, ready, Future};
+    use std::pin::{pin, Pin};
+    use std::task::{Context, Poll, Waker};
+    use std::time::Duration;
+
+    use futures_core::Stream;
+    use terrors::common::Timeout;
+    use terrors::futures::{OneOfFutureExt, OneOfStreamExt};
+
+    fn block_on<F: Future>(fut: F) -> F::Output {
+        let mut cx = Context::from_waker(Waker::noop());
+        let Poll::Ready(output) = pin!(fut).poll(&mut cx) else {
+            unreachable!()
+        };
+        output
+    }
+
+    fn is_send<T: Send>(t: T) -> T {
+        t
+    }
+
+    type E = OneOf<(NotEnoughMemory, RetriesExhausted)>;
+
+    let failing = || ready(Err::<u8, E>(OneOf::new(RetriesExhausted)));
+
+    let broadened: Result<u8, OneOf<(RetriesExhausted, Timeout, NotEnoughMemory)>> =
+        block_on(is_send(failing().broaden_err()));
+    assert!(broadened.is_err());
+
+    let narrowed: Result<Result<u8, RetriesExhausted>, OneOf<(NotEnoughMemory,)>> =
+        block_on(failing().narrow_err());
+    assert!(matches!(narrowed, Ok(Err(RetriesExhausted))));
+
+    let handled: Result<u8, OneOf<(NotEnoughMemory,)>> =
+        block_on(failing().handle_err(|_: RetriesExhausted| 3));
+    assert_eq!(handled.unwrap(), 3);
+
+    let mapped: Result<u8, OneOf<(NotEnoughMemory, Timeout)>> =
+        block_on(failing().map_variant(|_: RetriesExhausted| Timeout::new()));
+    assert!(mapped.unwrap_err().narrow::<Timeout, _>().is_ok());
+
+    let timed_out: Result<u8, OneOf<(NotEnoughMemory, RetriesExhausted, Timeout)>> =
+        block_on(pending::<Result<u8, E>>().timeout(Duration::from_secs(1), |_| ready(())));
+    let timeout = timed_out.unwrap_err().narrow::<Timeout, _>().unwrap();
+    assert_eq!(timeout.duration(), Some(Duration::from_secs(1)));
+
+    let completed: Result<u8, OneOf<(NotEnoughMemory, RetriesExhausted, Timeout)>> =
+        block_on(ready(Ok::<u8, E>(1)).timeout(Duration::ZERO, |_| ready(())));
+    assert_eq!(completed.unwrap(), 1);
+
+    // the outer timeout's `Timeout` is merged with the one already in the set
+    type Timed = (NotEnoughMemory, RetriesExhausted, Timeout);
+    let layered: Result<u8, OneOf<Timed>> = block_on(
+        pending::<Result<u8, E>>()
+            .timeout::<Timed, _, _, _, _>(Duration::from_secs(5), |_| pending())
+            .timeout(Duration::from_secs(1), |_| ready(())),
+    );
+    let timeout = layered.unwrap_err().narrow::<Timeout, _>().unwrap();
+    assert_eq!(timeout.duration(), Some(Duration::from_secs(1)));
+
+    struct Iter<I>(I);
+
+    impl<I: Iterator + Unpin> Stream for Iter<I> {
+        type Item = I::Item;
+
+        fn poll_next(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<I::Item>> {
+            Poll::Ready(self.0.next())
+        }
+    }
+
+    let items: Vec<Result<u8, E>> = vec![
+        Ok(1),
+        Err(OneOf::new(RetriesExhausted)),
+        Err(OneOf::new(NotEnoughMemory)),
+        Ok(2),
+    ];
+    let (oks, exhausted, rest) =
+        block_on(Iter(items.into_iter()).partition_err::<RetriesExhausted, _>());
+    assert_eq!(oks, [1, 2]);
+    assert_eq!(exhausted.len(), 1);
+    let rest: Vec<OneOf<(NotEnoughMemory,)>> = rest;
+    assert_eq!(rest.len(), 1);
+}
+
+#[test]
+fn try_join() {
+    use std::future::{pending, ready, Future};
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    use terrors::join::{settle3, try_join2, try_join3};
+
+    fn block_on<F: Future>(fut: F) -> F::Output {
+        let mut cx = Context::from_waker(Waker::noop());
+        let Poll::Ready(output) = pin!(fut).poll(&mut cx) else {
+            unreachable!()
+        };
+        output
+    }
+
+    let joined: Result<(u8, u16, u32), OneOf<(NotEnoughMemory, Timeout, RetriesExhausted)>> =
+        block_on(try_join3(
+            ready(Ok::<_, OneOf<(NotEnoughMemory, Timeout)>>(1)),
+            ready(Ok::<_, OneOf<(RetriesExhausted,)>>(2)),
+            ready(Ok::<_, Timeout>(3)),
+        ));
+    assert_eq!(joined.unwrap(), (1, 2, 3));
+
+    // the first failure is returned without waiting for the other branches
+    let failed: Result<(u8, u16), OneOf<(NotEnoughMemory, RetriesExhausted)>> =
+        block_on(try_join2(
+            pending::<Result<u8, NotEnoughMemory>>(),
+            ready(Err::<u16, _>(RetriesExhausted)),
+        ));
+    assert!(failed.unwrap_err().narrow::<RetriesExhausted, _>().is_ok());
+
+    let settled: Result<(u8, u16, u32), Vec<OneOf<(NotEnoughMemory, Timeout, RetriesExhausted)>>> =
+        block_on(settle3(
+            ready(Err::<u8, _>(OneOf::<(NotEnoughMemory, Timeout)>::new(
+                Timeout,
+            ))),
+            ready(Ok::<_, OneOf<(RetriesExhausted,)>>(2)),
+            ready(Err::<u32, _>(NotEnoughMemory)),
+        ));
+    let errors = settled.unwrap_err();
+    assert_eq!(errors.len(), 2);
+    assert!(errors[0].narrow_ref::<Timeout, _>().is_some());
+    assert!(errors[1].narrow_ref::<NotEnoughMemory, _>().is_some());
+}
+
+#[test]
+fn error_bag() {
+    use terrors::{CollectErrors, ErrorBag};
+
+    #[derive(Debug, PartialEq)]
+    struct Empty(&'static str);
+
+    #[derive(Debug, PartialEq)]
+    struct Negative(i32);
+
+    impl std::fmt::Display for Empty {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{} is empty", self.0)
+        }
+    }
+
+    impl std::fmt::Display for Negative {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{} is negative", self.0)
+        }
+    }
+
+    let mut bag: ErrorBag<(Empty, Negative, NotEnoughMemory)> = ErrorBag::new();
+    assert!(bag.is_empty());
+
+    bag.push(Empty("name"));
+    bag.push(OneOf::<(Negative,)>::new(Negative(-3)));
+    bag.extend([OneOf::new(Empty("email"))]);
+
+    assert_eq!(bag.len(), 3);
+    assert_eq!(
+        bag.iter_of::<Empty, _>().collect::<Vec<_>>(),
+        [&Empty("name"), &Empty("email")]
+    );
+
+    let (empties, rest) = bag.narrow_all::<Empty, _>();
+    assert_eq!(empties, [Empty("name"), Empty("email")]);
+    let rest: ErrorBag<(Negative, NotEnoughMemory)> = rest;
+    assert_eq!(rest.len(), 1);
+
+    let results: Vec<Result<u8, OneOf<(Empty, Negative)>>> = vec![
+        Ok(1),
+        Err(OneOf::new(Empty("name"))),
+        Err(OneOf::new(Negative(-1))),
+    ];
+    let bag: ErrorBag<(Empty, Negative)> = results.into_iter().collect_errors().unwrap_err();
+    assert_eq!(
+        bag.to_string(),
+        "2 errors:\n  name is empty\n  -1 is negative"
+    );
+
+    let ok: Result<Vec<u8>, ErrorBag<(Empty, Negative)>> = vec![Ok::<u8, Negative>(1), Ok(2)]
+        .into_iter()
+        .collect_errors();
+    assert_eq!(ok.unwrap(), [1, 2]);
+}
+
+#[test]
+fn suppressed_errors() {
+    use terrors::WithSuppressed;
+
+    #[derive(Debug)]
+    struct RollbackFailed;
+
+    #[derive(Debug)]
+    struct UnlockFailed;
+
+    let primary: OneOf<(Timeout, NotEnoughMemory)> = OneOf::new(Timeout);
+    let mut error: WithSuppressed<_, (RollbackFailed, UnlockFailed)> = WithSuppressed::new(primary);
+    error.suppress(RollbackFailed);
+    let error = error.with_suppressed(OneOf::<(UnlockFailed,)>::new(UnlockFailed));
+
+    assert!(error.narrow_ref::<Timeout, _>().is_some());
+    assert_eq!(error.suppressed().count(), 2);
+    assert_eq!(format!("{error:?}"), "Timeout");
+    assert!(format!("{error:#?}").contains("RollbackFailed"));
+
+    let broadened: WithSuppressed<(RetriesExhausted, NotEnoughMemory, Timeout), _> =
+        error.broaden();
+    let rest = broadened.narrow::<RetriesExhausted, _>().unwrap_err();
+    assert_eq!(rest.suppressed().count(), 2);
+
+    let timeout = rest.narrow::<Timeout, _>().unwrap();
+    let (timeout, suppressed) = timeout.into_parts();
+    assert!(matches!(*timeout, Timeout));
+    assert_eq!(suppressed.iter_of::<UnlockFailed, _>().count(), 1);
+}